@@ -0,0 +1,382 @@
+#![allow(dead_code)]
+
+// The algebra the matrix code needs from its entries. Everything in mats is written against these
+// traits, so the same elimination code works for fractions, floats and modular integers.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use fracs;
+use fracs::{Frac, WideFrac};
+
+pub trait Ring: Copy + PartialEq + fmt::Debug + fmt::Display {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn mul(self, other: Self) -> Self;
+
+    // Every ring has a map from the integers; used for small constants like signs and identity
+    // entries
+    fn from_i32(n: i32) -> Self;
+
+    fn negative(self) -> Self {
+        Self::zero().sub(self)
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    fn is_one(&self) -> bool {
+        *self == Self::one()
+    }
+
+    // Only used to pick a nicer sign when printing steps. Rings without an ordering just never
+    // have negative elements.
+    fn is_negative(&self) -> bool {
+        false
+    }
+
+    // What to multiply a row by so every entry is an integer, for fraction-free elimination.
+    // Rings whose elements don't grow during elimination never need scaling. None if the factor
+    // itself doesn't fit.
+    fn integer_scale(_row: &[Self]) -> Option<Self> {
+        Some(Self::one())
+    }
+}
+
+pub trait Field: Ring {
+    // The same numbers with more room, for long computations whose intermediate values outgrow
+    // the entries even when the answer doesn't. Types that can't get any wider use themselves.
+    type Wide: Field;
+
+    fn widen(self) -> Self::Wide;
+
+    // None if the value doesn't fit back into Self
+    fn narrow(wide: Self::Wide) -> Option<Self>;
+
+    // Only ever called on nonzero elements
+    fn inverse(self) -> Self;
+
+    fn div(self, other: Self) -> Self {
+        self.mul(other.inverse())
+    }
+}
+
+impl Ring for Frac {
+    fn zero() -> Self {
+        Frac::from(0)
+    }
+
+    fn one() -> Self {
+        Frac::from(1)
+    }
+
+    fn add(self, other: Frac) -> Frac {
+        Frac::add(self, other)
+    }
+
+    fn sub(self, other: Frac) -> Frac {
+        Frac::sub(self, other)
+    }
+
+    fn mul(self, other: Frac) -> Frac {
+        Frac::mul(self, other)
+    }
+
+    fn from_i32(n: i32) -> Self {
+        Frac::from(n)
+    }
+
+    fn negative(self) -> Frac {
+        Frac::negative(self)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    fn is_negative(&self) -> bool {
+        self.num != 0 && (self.num < 0) != (self.den < 0)
+    }
+
+    // lcm of the denominators, as long as it stays inside an i32
+    fn integer_scale(row: &[Frac]) -> Option<Frac> {
+        let lcm = row.iter().try_fold(1i64, |acc, elem| {
+            let den = (elem.den as i64).abs();
+            let lcm = acc / fracs::get_gcd(acc as u32, den as u32) as i64 * den;
+            if lcm > i32::MAX as i64 { None } else { Some(lcm) }
+        })?;
+        Some(Frac::from(lcm as i32))
+    }
+}
+
+impl Field for Frac {
+    type Wide = WideFrac;
+
+    fn widen(self) -> WideFrac {
+        WideFrac::from(self)
+    }
+
+    fn narrow(wide: WideFrac) -> Option<Frac> {
+        Frac::try_from(wide).ok()
+    }
+
+    fn inverse(self) -> Frac {
+        Frac::inverse(self)
+    }
+
+    fn div(self, other: Frac) -> Frac {
+        Frac::div(self, other)
+    }
+}
+
+impl Ring for WideFrac {
+    fn zero() -> Self {
+        WideFrac::from(0)
+    }
+
+    fn one() -> Self {
+        WideFrac::from(1)
+    }
+
+    fn add(self, other: WideFrac) -> WideFrac {
+        WideFrac::add(self, other)
+    }
+
+    fn sub(self, other: WideFrac) -> WideFrac {
+        WideFrac::sub(self, other)
+    }
+
+    fn mul(self, other: WideFrac) -> WideFrac {
+        WideFrac::mul(self, other)
+    }
+
+    fn from_i32(n: i32) -> Self {
+        WideFrac::from(n as i64)
+    }
+
+    fn negative(self) -> WideFrac {
+        WideFrac::negative(self)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    fn is_negative(&self) -> bool {
+        self.num < 0
+    }
+
+    fn integer_scale(row: &[WideFrac]) -> Option<WideFrac> {
+        let lcm = row.iter().try_fold(1i128, |acc, elem| {
+            let (mut a, mut b) = (acc, elem.den as i128);
+            while b != 0 {
+                let c = b;
+                b = a % b;
+                a = c;
+            }
+            let lcm = acc / a * elem.den as i128;
+            if lcm > i64::MAX as i128 { None } else { Some(lcm) }
+        })?;
+        Some(WideFrac::from(lcm as i64))
+    }
+}
+
+impl Field for WideFrac {
+    type Wide = WideFrac;
+
+    fn widen(self) -> WideFrac {
+        self
+    }
+
+    fn narrow(wide: WideFrac) -> Option<WideFrac> {
+        Some(wide)
+    }
+
+    fn inverse(self) -> WideFrac {
+        WideFrac::inverse(self)
+    }
+}
+
+// Floats aren't exact, so anything within EPSILON of zero counts as zero during elimination
+impl Ring for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn one() -> Self {
+        1.0
+    }
+
+    fn add(self, other: f64) -> f64 {
+        self + other
+    }
+
+    fn sub(self, other: f64) -> f64 {
+        self - other
+    }
+
+    fn mul(self, other: f64) -> f64 {
+        self * other
+    }
+
+    fn from_i32(n: i32) -> Self {
+        n as f64
+    }
+
+    fn is_zero(&self) -> bool {
+        self.abs() < EPSILON
+    }
+
+    fn is_negative(&self) -> bool {
+        *self < 0.0
+    }
+}
+
+impl Field for f64 {
+    type Wide = f64;
+
+    fn widen(self) -> f64 {
+        self
+    }
+
+    fn narrow(wide: f64) -> Option<f64> {
+        Some(wide)
+    }
+
+    fn inverse(self) -> f64 {
+        1.0 / self
+    }
+
+    fn div(self, other: f64) -> f64 {
+        self / other
+    }
+}
+
+pub const EPSILON: f64 = 1e-12;
+
+// Integers mod P. Only a field when P is prime - for composite P, `inverse` panics on zero
+// divisors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModInt<const P: u32> {
+    pub val: u32
+}
+
+impl<const P: u32> fmt::Display for ModInt<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+impl<const P: u32> From<i64> for ModInt<P> {
+    fn from(n: i64) -> Self {
+        ModInt {
+            val: n.rem_euclid(P as i64) as u32
+        }
+    }
+}
+
+impl<const P: u32> Ring for ModInt<P> {
+    fn zero() -> Self {
+        ModInt { val: 0 }
+    }
+
+    fn one() -> Self {
+        ModInt::from(1)
+    }
+
+    fn add(self, other: Self) -> Self {
+        ModInt::from(self.val as i64 + other.val as i64)
+    }
+
+    fn sub(self, other: Self) -> Self {
+        ModInt::from(self.val as i64 - other.val as i64)
+    }
+
+    fn mul(self, other: Self) -> Self {
+        ModInt::from(self.val as i64 * other.val as i64)
+    }
+
+    fn from_i32(n: i32) -> Self {
+        ModInt::from(n as i64)
+    }
+}
+
+impl<const P: u32> Field for ModInt<P> {
+    type Wide = ModInt<P>;
+
+    fn widen(self) -> Self {
+        self
+    }
+
+    fn narrow(wide: Self) -> Option<Self> {
+        Some(wide)
+    }
+
+    fn inverse(self) -> Self {
+        match fracs::mod_inverse(self.val as i64, P as i64) {
+            Some(inv) => ModInt::from(inv),
+            None => panic!("{} has no inverse mod {}.", self.val, P)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use field::*;
+    use fracs::{Frac, WideFrac};
+    use mats::Matrix;
+    use mats::format::Silent;
+
+    type Z7 = ModInt<7>;
+
+    #[test]
+    fn mod_int_arithmetic() {
+        let (three, five) = (Z7::from(3), Z7::from(5));
+        assert_eq!(Z7::from(-1), Z7::from(6));
+        assert_eq!(three.add(five), Z7::one());
+        assert_eq!(three.sub(five), Z7::from(5));
+        assert_eq!(three.mul(five), Z7::one());
+        assert_eq!(three.inverse(), five);
+        assert_eq!(Z7::from(2).div(three), Z7::from(3));
+        assert_eq!(three.negative(), Z7::from(4));
+        assert!(Z7::from(14).is_zero());
+    }
+
+    #[test]
+    fn same_inverse_code_over_every_field() {
+        // det = -2, which is 5 mod 7
+        let a = Matrix::from_rows(vec![vec![Z7::from(1), Z7::from(2)], vec![Z7::from(3), Z7::from(4)]]).unwrap();
+        let expected = Matrix::from_rows(vec![vec![Z7::from(5), Z7::from(1)], vec![Z7::from(5), Z7::from(3)]]).unwrap();
+        assert_eq!(a.inverse(&mut Silent), Ok(expected));
+
+        let b = "4, 7; 2, 6".parse::<Matrix<f64>>().unwrap().inverse(&mut Silent).unwrap();
+        let expected = [0.6, -0.7, -0.2, 0.4];
+        assert!(b.to_flat().iter().zip(expected.iter()).all(|(x, y)| (x - y).abs() < EPSILON));
+
+        let c = "4, 7; 2, 6".parse::<Matrix<Frac>>().unwrap().inverse(&mut Silent).unwrap();
+        let wide = c.map(Frac::widen);
+        assert_eq!(wide.to_flat()[1], WideFrac::new(-7, 10));
+        assert_eq!(wide.to_frac_matrix(), Ok(c));
+    }
+
+    #[test]
+    fn integer_scale_is_the_lcm_of_the_denominators() {
+        assert_eq!(Frac::integer_scale(&[Frac::new(1, 2), Frac::new(-1, 3), Frac::from(5)]), Some(Frac::from(6)));
+        assert_eq!(Frac::integer_scale(&[Frac::new(1, 65536), Frac::new(1, 65537)]), None);
+        assert_eq!(WideFrac::integer_scale(&[WideFrac::new(1, 65536), WideFrac::new(1, 65537)]),
+                   Some(WideFrac::from(65536 * 65537)));
+        assert_eq!(f64::integer_scale(&[0.5, 0.25]), Some(1.0));
+    }
+
+    #[test]
+    fn widen_and_narrow() {
+        let x = Frac::new(-3, 8);
+        assert_eq!(Frac::narrow(x.widen()), Some(x));
+        assert_eq!(Frac::narrow(WideFrac::new(1 << 40, 3)), None);
+        assert_eq!(Frac::narrow(WideFrac::new(1 << 40, 1 << 39)), Some(Frac::from(2)));
+        assert_eq!(Z7::narrow(Z7::from(4).widen()), Some(Z7::from(4)));
+    }
+}
@@ -1,8 +1,10 @@
-#![allow(dead_code, non_snake_case_crates)]
+#![allow(dead_code, non_snake_case)]
 
 use std::fmt;
+use std::convert::TryFrom;
+use std::str::FromStr;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct Frac {
     pub num: i32,
     pub den: i32
@@ -10,44 +12,17 @@ pub struct Frac {
 
 impl PartialEq for Frac {
     fn eq(&self, other: &Frac) -> bool {
-        match self.cmp(other) {
-            CmpRes::Eq => true,
-            _ => false
-        }
+        matches!(self.cmp(other), CmpRes::Eq)
     }
 }
 
+#[derive(PartialEq)]
 pub enum CmpRes {
     Eq,
     Lt,
     Gt
 }
 
-impl PartialEq for CmpRes {
-    fn eq(&self, other: &CmpRes) -> bool {
-        match self {
-            &CmpRes::Eq => {
-                match other {
-                    &CmpRes::Eq => true,
-                    _ => false
-                }
-            },
-            &CmpRes::Lt => {
-                match other {
-                    &CmpRes::Lt => true,
-                    _ => false
-                }
-            },
-            &CmpRes::Gt => {
-                match other {
-                    &CmpRes::Gt => true,
-                    _ => false
-                }
-            }
-        }
-    }
-}
-
 impl fmt::Display for Frac {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if self.den == 1 {
@@ -57,10 +32,29 @@ impl fmt::Display for Frac {
     }
 }
 
+// Accepts "3", "-3/4" and "-3 / 4" (the same format Display writes)
+impl FromStr for Frac {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Frac, String> {
+        let mut parts = string.split('/').map(str::trim);
+        let num = parts.next().and_then(|num| num.parse::<i32>().ok());
+        let den = match parts.next() {
+            Some(den) => den.parse::<i32>().ok(),
+            None => Some(1)
+        };
+        match (num, den, parts.next()) {
+            (Some(_), Some(0), None) => Err(format!("\"{}\" has a denominator of zero.", string)),
+            (Some(num), Some(den), None) => Ok(Frac::new(num, den)),
+            _ => Err(format!("\"{}\" isn't a fraction.", string))
+        }
+    }
+}
+
 impl From<i32> for Frac {
     fn from(num: i32) -> Self {
         Frac {
-            num: num,
+            num,
             den: 1
         }
     }
@@ -68,12 +62,12 @@ impl From<i32> for Frac {
 
 impl Frac {
     pub fn new(num: i32, den: i32) -> Self {
-        if (num == 0 && den == 0) || den == 0 {
+        if den == 0 {
             panic!("Tried to create a fraction with a numerator or denominator of zero. Use a scalar for zero.");
         }
         Frac {
-            num: num,
-            den: den
+            num,
+            den
         }.try_simplify()
     }
 
@@ -86,9 +80,7 @@ impl Frac {
     }
 
     pub fn inverse(mut self) -> Frac {
-        let temp = self.num;
-        self.num = self.den;
-        self.den = temp;
+        std::mem::swap(&mut self.num, &mut self.den);
         self.try_simplify()
     }
 
@@ -139,7 +131,7 @@ impl Frac {
             self.num += other.num;
             self.try_simplify()
         } else {
-            let lcm = get_lcm(self.den, other.den) as i32;
+            let lcm = get_lcm(self.den, other.den);
             let self_mult = lcm / self.den;
             let other_mult = lcm / other.den;
             self.num *= self_mult;
@@ -168,7 +160,7 @@ impl Frac {
     pub fn sub(mut self, other: Frac) -> Frac {
         if self.den == other.den { // ez case
             self.num -= other.num;
-            return self.try_simplify();
+            self.try_simplify()
         } else {
             let lcm = get_lcm(self.den, other.den);
             let self_mult = lcm / self.den;
@@ -190,18 +182,82 @@ impl Frac {
                 return CmpRes::Lt;
             }
         }
-        // Compare numerators for equal denominators
-        let lcm = get_lcm(self.den, other.den);
-        let self_lcm = self.num * lcm / self.den;
-        let other_lcm = other.num * lcm / other.den;
-        if self_lcm < other_lcm {
+        // Cross-multiply in 64 bits so big denominators can't overflow. A negative denominator on
+        // just one side flips the comparison.
+        let (mut lhs, mut rhs) = (self.num as i64 * other.den as i64, other.num as i64 * self.den as i64);
+        if (self.den < 0) != (other.den < 0) {
+            std::mem::swap(&mut lhs, &mut rhs);
+        }
+        if lhs < rhs {
             CmpRes::Lt
-        } else if self_lcm == other_lcm {
+        } else if lhs == rhs {
             CmpRes::Eq
         } else {
             CmpRes::Gt
         }
     }
+
+    // p-adic valuation: the power of p in the numerator minus the power of p in the denominator.
+    // Zero is divisible by every power of p, so it has no (finite) valuation. None as well if p
+    // isn't prime.
+    pub fn valuation(&self, p: u32) -> Option<i32> {
+        if self.num == 0 {
+            return None;
+        }
+        Some(int_valuation(self.num as i64, p)? as i32 - int_valuation(self.den as i64, p)? as i32)
+    }
+
+    // |x|_p = p^(-v_p(x)), and |0|_p = 0. None if p isn't prime or p^|v| doesn't fit in an i32.
+    pub fn padic_abs(&self, p: u32) -> Option<Frac> {
+        if !is_prime(p) {
+            return None;
+        }
+        let v = match self.valuation(p) {
+            None => return Some(Frac::from(0)),
+            Some(v) => v
+        };
+        let power = i32::try_from(p).ok()?.checked_pow(v.unsigned_abs())?;
+        if v >= 0 {
+            Some(Frac::new(1, power))
+        } else {
+            Some(Frac::from(power))
+        }
+    }
+
+    // First `digits` digits of the p-adic expansion. Pulls the factors of p out first so the digits
+    // are those of the unit part, then peels off one digit at a time:
+    // d = a * b^(-1) mod p, a / b → (a / b - d) / p. With a negative valuation it keeps going at
+    // least as far as the units digit, so the point always has somewhere to go.
+    pub fn padic_expansion(&self, p: u32, digits: usize) -> Result<PAdic, String> {
+        if !is_prime(p) {
+            return Err(format!("{} is not prime, so there's no {0}-adic expansion.", p));
+        }
+        let valuation = match self.valuation(p) {
+            Some(v) => v,
+            None => return Err(String::from("Zero has no p-adic expansion with a leading digit."))
+        };
+        let (mut a, mut b) = (self.num as i64, self.den as i64);
+        let p64 = p as i64;
+        while a % p64 == 0 {
+            a /= p64;
+        }
+        while b % p64 == 0 {
+            b /= p64;
+        }
+        let b_inv = mod_inverse(b, p64).unwrap();
+        let digits = if valuation < 0 { digits.max((1 - valuation) as usize) } else { digits };
+        let mut ret = Vec::with_capacity(digits);
+        for _ in 0..digits {
+            let d = (a % p64 + p64) % p64 * b_inv % p64;
+            ret.push(d as u32);
+            a = (a - d * b) / p64;
+        }
+        Ok(PAdic {
+            p,
+            valuation,
+            digits: ret
+        })
+    }
 }
 
 // Not using Euclid's Algorithm anymore because it's really slow >:v
@@ -229,4 +285,500 @@ pub fn get_lcm(a: i32, b: i32) -> i32 {
     };
     let gcd = get_gcd(ayy, bee);
     (ayy * bee / gcd) as i32
-}
\ No newline at end of file
+}
+
+// A truncated p-adic number p^valuation * (d0 + d1 * p + d2 * p^2 + ...)
+#[derive(Clone)]
+pub struct PAdic {
+    pub p: u32,
+    pub valuation: i32,
+    pub digits: Vec<u32>
+}
+
+// Written the usual way round, most significant (known) digit first: ...d2 d1 d0, with trailing
+// zeros for a positive valuation and a point for a negative one. Too few digits to reach the point
+// get padded with zeros up to the units digit.
+impl fmt::Display for PAdic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sep = if self.p > 10 { " " } else { "" };
+        let mut digits = self.digits.clone();
+        let mut point = 0;
+        if self.valuation > 0 {
+            let mut shifted = vec![0; self.valuation as usize];
+            shifted.append(&mut digits);
+            digits = shifted;
+        } else {
+            point = (-self.valuation) as usize;
+            if digits.len() <= point {
+                digits.resize(point + 1, 0);
+            }
+        }
+        let mut ret = String::from("…");
+        for i in (0..digits.len()).rev() {
+            ret = format!("{}{}", ret, digits[i]);
+            if i == point && point != 0 {
+                ret = format!("{}.", ret);
+            } else if i != 0 {
+                ret = format!("{}{}", ret, sep);
+            }
+        }
+        write!(f, "{} ({}-adic)", ret, self.p)
+    }
+}
+
+// Number of times p divides n, or None if p isn't prime (p = 0 or 1 would never terminate)
+pub fn int_valuation(mut n: i64, p: u32) -> Option<u32> {
+    if !is_prime(p) {
+        return None;
+    }
+    let p = p as i64;
+    let mut v = 0;
+    while n != 0 && n % p == 0 {
+        n /= p;
+        v += 1;
+    }
+    Some(v)
+}
+
+fn is_prime(p: u32) -> bool {
+    p >= 2 && (2u64..).take_while(|d| d * d <= p as u64).all(|d| !(p as u64).is_multiple_of(d))
+}
+
+// Extended Euclid - a^(-1) mod m, if a and m are coprime
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (mut old_r, mut r) = ((a % m + m) % m, m);
+    let (mut old_s, mut s) = (1i64, 0i64);
+    while r != 0 {
+        let q = old_r / r;
+        let tmp_r = old_r - q * r;
+        old_r = r;
+        r = tmp_r;
+        let tmp_s = old_s - q * s;
+        old_s = s;
+        s = tmp_s;
+    }
+    if old_r != 1 {
+        return None;
+    }
+    Some((old_s % m + m) % m)
+}
+
+// Evaluates the polynomial with coefficients `coeffs` (constant term first) at x, mod m
+fn poly_eval_mod(coeffs: &[i64], x: i64, m: i64) -> i64 {
+    let mut ret: i128 = 0;
+    for &c in coeffs.iter().rev() {
+        ret = (ret * x as i128 + c as i128) % m as i128;
+    }
+    ((ret + m as i128) % m as i128) as i64
+}
+
+fn poly_derivative(coeffs: &[i64]) -> Vec<i64> {
+    coeffs.iter().enumerate().skip(1).map(|(i, &c)| c * i as i64).collect()
+}
+
+// Hensel lifting: given a simple root of f mod p (f(r) ≡ 0 and f'(r) ≢ 0 mod p), lifts it one power
+// of p at a time to the unique root of f mod p^k that agrees with it mod p.
+// `coeffs` holds the coefficients of f, constant term first.
+pub fn hensel_lift(coeffs: &[i64], root: i64, p: u32, k: u32) -> Result<i64, String> {
+    let p = p as i64;
+    let deriv = poly_derivative(coeffs);
+    if poly_eval_mod(coeffs, root, p) != 0 {
+        return Err(format!("{} is not a root of the polynomial mod {}.", root, p));
+    }
+    // f'(r) mod p never changes while lifting, since every lift agrees with r mod p
+    let deriv_inv = match mod_inverse(poly_eval_mod(&deriv, root, p), p) {
+        Some(inv) => inv,
+        None => return Err(format!("{} is a repeated root mod {}, so it can't be lifted uniquely.", root, p))
+    };
+    let mut modulus = p;
+    let mut r = (root % p + p) % p;
+    for _ in 1..k {
+        let next_modulus = match modulus.checked_mul(p) {
+            Some(m) => m,
+            None => return Err(String::from("p^k is too large to lift to."))
+        };
+        // f(r) ≡ 0 mod p^j, so f(r) / p^j is an integer t and the next digit is -t * f'(r)^(-1) mod p
+        let t = poly_eval_mod(coeffs, r, next_modulus) / modulus;
+        let digit = ((-t % p + p) % p) * deriv_inv % p;
+        r += digit * modulus;
+        modulus = next_modulus;
+    }
+    Ok(r)
+}
+
+// All roots of f mod p^k that lift from simple roots mod p. Roots mod p are found by brute force,
+// so keep p small.
+pub fn solve_congruence(coeffs: &[i64], p: u32, k: u32) -> Result<Vec<i64>, String> {
+    if !is_prime(p) {
+        return Err(format!("{} is not prime.", p));
+    }
+    let mut ret = Vec::new();
+    for r in (0..p as i64).filter(|&r| poly_eval_mod(coeffs, r, p as i64) == 0) {
+        if let Ok(lifted) = hensel_lift(coeffs, r, p, k) {
+            ret.push(lifted);
+        }
+    }
+    Ok(ret)
+}
+
+// Projectively extended rationals: Q plus a single unsigned infinity (so -∞ = ∞), plus an
+// undefined value for the forms that have no sensible answer (0/0, ∞ + ∞, 0 * ∞, ∞ / ∞). Handy
+// for slopes of vertical lines and for Möbius transformations, where x/0 = ∞ is exactly what you
+// want instead of a panic.
+#[derive(Clone, Copy, Debug)]
+pub enum ExtFrac {
+    Finite(Frac),
+    Infinity,
+    Undefined
+}
+
+// Structural equality, so `Undefined == Undefined`. This isn't NaN - it's just a marker that
+// something went wrong further up.
+impl PartialEq for ExtFrac {
+    fn eq(&self, other: &ExtFrac) -> bool {
+        match (*self, *other) {
+            (ExtFrac::Finite(a), ExtFrac::Finite(b)) => a == b,
+            (ExtFrac::Infinity, ExtFrac::Infinity) => true,
+            (ExtFrac::Undefined, ExtFrac::Undefined) => true,
+            _ => false
+        }
+    }
+}
+
+impl fmt::Display for ExtFrac {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ExtFrac::Finite(frac) => write!(f, "{}", frac),
+            ExtFrac::Infinity => write!(f, "∞"),
+            ExtFrac::Undefined => write!(f, "undefined")
+        }
+    }
+}
+
+impl From<Frac> for ExtFrac {
+    fn from(frac: Frac) -> Self {
+        ExtFrac::Finite(frac)
+    }
+}
+
+impl From<i32> for ExtFrac {
+    fn from(num: i32) -> Self {
+        ExtFrac::Finite(Frac::from(num))
+    }
+}
+
+impl TryFrom<ExtFrac> for Frac {
+    type Error = String;
+
+    fn try_from(ext: ExtFrac) -> Result<Frac, String> {
+        match ext {
+            ExtFrac::Finite(frac) => Ok(frac),
+            ExtFrac::Infinity => Err(String::from("Can't convert ∞ into a fraction.")),
+            ExtFrac::Undefined => Err(String::from("Can't convert an undefined value into a fraction."))
+        }
+    }
+}
+
+impl ExtFrac {
+    // Unlike `Frac::new` this never panics: x/0 is ∞ and 0/0 is undefined
+    pub fn new(num: i32, den: i32) -> Self {
+        if den != 0 {
+            return ExtFrac::Finite(Frac::new(num, den));
+        }
+        if num == 0 {
+            ExtFrac::Undefined
+        } else {
+            ExtFrac::Infinity
+        }
+    }
+
+    // Slope of the line through two points, ∞ for a vertical line and undefined if the points are
+    // the same
+    pub fn slope(p1: (Frac, Frac), p2: (Frac, Frac)) -> Self {
+        ExtFrac::from(p2.1.sub(p1.1)).div(ExtFrac::from(p2.0.sub(p1.0)))
+    }
+
+    pub fn is_finite(&self) -> bool {
+        matches!(*self, ExtFrac::Finite(_))
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        matches!(*self, ExtFrac::Infinity)
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        matches!(*self, ExtFrac::Undefined)
+    }
+
+    pub fn to_frac(self) -> Result<Frac, String> {
+        Frac::try_from(self)
+    }
+
+    pub fn inverse(self) -> ExtFrac {
+        match self {
+            ExtFrac::Finite(frac) if frac.num == 0 => ExtFrac::Infinity,
+            ExtFrac::Finite(frac) => ExtFrac::Finite(frac.inverse()),
+            ExtFrac::Infinity => ExtFrac::Finite(Frac::from(0)),
+            ExtFrac::Undefined => ExtFrac::Undefined
+        }
+    }
+
+    // There's only the one infinity, so it's its own negative
+    pub fn negative(self) -> ExtFrac {
+        match self {
+            ExtFrac::Finite(frac) => ExtFrac::Finite(frac.negative()),
+            other => other
+        }
+    }
+
+    pub fn add(self, other: ExtFrac) -> ExtFrac {
+        match (self, other) {
+            (ExtFrac::Finite(a), ExtFrac::Finite(b)) => ExtFrac::Finite(a.add(b)),
+            (ExtFrac::Undefined, _) | (_, ExtFrac::Undefined) => ExtFrac::Undefined,
+            (ExtFrac::Infinity, ExtFrac::Infinity) => ExtFrac::Undefined,
+            _ => ExtFrac::Infinity
+        }
+    }
+
+    pub fn sub(self, other: ExtFrac) -> ExtFrac {
+        self.add(other.negative())
+    }
+
+    pub fn mul(self, other: ExtFrac) -> ExtFrac {
+        match (self, other) {
+            (ExtFrac::Finite(a), ExtFrac::Finite(b)) => ExtFrac::Finite(a.mul(b)),
+            (ExtFrac::Undefined, _) | (_, ExtFrac::Undefined) => ExtFrac::Undefined,
+            (ExtFrac::Finite(zero), ExtFrac::Infinity) | (ExtFrac::Infinity, ExtFrac::Finite(zero))
+                if zero.num == 0 => ExtFrac::Undefined,
+            _ => ExtFrac::Infinity
+        }
+    }
+
+    pub fn div(self, other: ExtFrac) -> ExtFrac {
+        match (self, other) {
+            (ExtFrac::Finite(a), ExtFrac::Finite(b)) if a.num == 0 && b.num == 0 => ExtFrac::Undefined,
+            (ExtFrac::Infinity, ExtFrac::Infinity) => ExtFrac::Undefined,
+            _ => self.mul(other.inverse())
+        }
+    }
+
+    // Applies the Möbius transformation x ↦ (a * x + b) / (c * x + d). The transformation is only
+    // a bijection of the extended line when ad - bc ≠ 0, so that's checked up front.
+    pub fn mobius(self, a: Frac, b: Frac, c: Frac, d: Frac) -> Result<ExtFrac, String> {
+        if a.mul(d).sub(b.mul(c)).num == 0 {
+            return Err(String::from("Möbius transformation is degenerate (ad - bc = 0)."));
+        }
+        match self {
+            // f(∞) = a / c, which is ∞ when c = 0
+            ExtFrac::Infinity => Ok(ExtFrac::from(a).div(ExtFrac::from(c))),
+            ExtFrac::Undefined => Ok(ExtFrac::Undefined),
+            ExtFrac::Finite(x) => Ok(ExtFrac::from(a.mul(x).add(b)).div(ExtFrac::from(c.mul(x).add(d))))
+        }
+    }
+}
+
+// Same idea as Frac but with 64 bit parts, for when i32 numerators and denominators overflow during
+// elimination. Always kept in lowest terms with a positive denominator, so equality is structural.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WideFrac {
+    pub num: i64,
+    pub den: i64
+}
+
+impl fmt::Display for WideFrac {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            return write!(f, "{}", self.num);
+        }
+        write!(f, "{} / {}", self.num, self.den)
+    }
+}
+
+impl From<i64> for WideFrac {
+    fn from(num: i64) -> Self {
+        WideFrac {
+            num,
+            den: 1
+        }
+    }
+}
+
+impl From<Frac> for WideFrac {
+    fn from(frac: Frac) -> Self {
+        WideFrac::new(frac.num as i64, frac.den as i64)
+    }
+}
+
+impl TryFrom<WideFrac> for Frac {
+    type Error = String;
+
+    fn try_from(wide: WideFrac) -> Result<Frac, String> {
+        match (i32::try_from(wide.num), i32::try_from(wide.den)) {
+            (Ok(num), Ok(den)) => Ok(Frac::new(num, den)),
+            _ => Err(format!("{} doesn't fit in a 32 bit fraction.", wide))
+        }
+    }
+}
+
+impl WideFrac {
+    pub fn new(num: i64, den: i64) -> Self {
+        if den == 0 {
+            panic!("Tried to create a fraction with a denominator of zero.");
+        }
+        WideFrac::reduce(num as i128, den as i128)
+    }
+
+    // Does the arithmetic in 128 bits and only panics if the reduced result doesn't fit in 64
+    fn reduce(mut num: i128, mut den: i128) -> WideFrac {
+        if den < 0 {
+            num = -num;
+            den = -den;
+        }
+        let (mut a, mut b) = (num.abs(), den);
+        while b != 0 {
+            let c = b;
+            b = a % b;
+            a = c;
+        }
+        if a > 1 {
+            num /= a;
+            den /= a;
+        }
+        match (i64::try_from(num), i64::try_from(den)) {
+            (Ok(num), Ok(den)) => WideFrac { num, den },
+            _ => panic!("WideFrac overflowed 64 bits.")
+        }
+    }
+
+    pub fn inverse(self) -> WideFrac {
+        WideFrac::new(self.den, self.num)
+    }
+
+    pub fn negative(self) -> WideFrac {
+        WideFrac {
+            num: -self.num,
+            den: self.den
+        }
+    }
+
+    pub fn add(self, other: WideFrac) -> WideFrac {
+        WideFrac::reduce(self.num as i128 * other.den as i128 + other.num as i128 * self.den as i128,
+                         self.den as i128 * other.den as i128)
+    }
+
+    pub fn sub(self, other: WideFrac) -> WideFrac {
+        self.add(other.negative())
+    }
+
+    pub fn mul(self, other: WideFrac) -> WideFrac {
+        WideFrac::reduce(self.num as i128 * other.num as i128, self.den as i128 * other.den as i128)
+    }
+
+    pub fn div(self, other: WideFrac) -> WideFrac {
+        self.mul(other.inverse())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fracs::*;
+
+    #[test]
+    fn ext_frac_division_by_zero() {
+        assert_eq!(ExtFrac::new(3, 0), ExtFrac::Infinity);
+        assert_eq!(ExtFrac::new(0, 0), ExtFrac::Undefined);
+        assert_eq!(ExtFrac::new(2, -4), ExtFrac::from(Frac::new(-1, 2)));
+        assert_eq!(ExtFrac::from(5).div(ExtFrac::from(0)), ExtFrac::Infinity);
+        assert_eq!(ExtFrac::from(0).inverse(), ExtFrac::Infinity);
+        assert_eq!(ExtFrac::Infinity.inverse(), ExtFrac::from(0));
+    }
+
+    #[test]
+    fn ext_frac_infinity_rules() {
+        let (inf, undef) = (ExtFrac::Infinity, ExtFrac::Undefined);
+        assert_eq!(inf.add(ExtFrac::from(7)), inf);
+        assert_eq!(inf.sub(inf), undef);
+        assert_eq!(inf.add(inf), undef);
+        assert_eq!(inf.negative(), inf);
+        assert_eq!(inf.mul(ExtFrac::from(-2)), inf);
+        assert_eq!(inf.mul(ExtFrac::from(0)), undef);
+        assert_eq!(inf.div(inf), undef);
+        assert_eq!(ExtFrac::from(0).div(ExtFrac::from(0)), undef);
+        assert_eq!(ExtFrac::from(3).div(inf), ExtFrac::from(0));
+        assert_eq!(undef.add(ExtFrac::from(1)), undef);
+        assert!(inf.to_frac().is_err());
+    }
+
+    #[test]
+    fn ext_frac_slope_and_mobius() {
+        let origin = (Frac::from(0), Frac::from(0));
+        assert_eq!(ExtFrac::slope(origin, (Frac::from(2), Frac::from(1))), ExtFrac::from(Frac::new(1, 2)));
+        assert_eq!(ExtFrac::slope(origin, (Frac::from(0), Frac::from(3))), ExtFrac::Infinity);
+        assert_eq!(ExtFrac::slope(origin, origin), ExtFrac::Undefined);
+        // x ↦ (2x + 1) / (x - 1): ∞ ↦ 2, 1 ↦ ∞
+        let (a, b, c, d) = (Frac::from(2), Frac::from(1), Frac::from(1), Frac::from(-1));
+        assert_eq!(ExtFrac::Infinity.mobius(a, b, c, d), Ok(ExtFrac::from(2)));
+        assert_eq!(ExtFrac::from(1).mobius(a, b, c, d), Ok(ExtFrac::Infinity));
+        assert_eq!(ExtFrac::from(3).mobius(a, b, c, d), Ok(ExtFrac::from(Frac::new(7, 2))));
+        assert!(ExtFrac::from(3).mobius(a, a, a, a).is_err());
+    }
+
+    #[test]
+    fn valuations() {
+        let x = Frac::new(50, 3);
+        assert_eq!(x.valuation(5), Some(2));
+        assert_eq!(x.valuation(3), Some(-1));
+        assert_eq!(x.valuation(7), Some(0));
+        assert_eq!(x.valuation(4), None);
+        assert_eq!(x.valuation(1), None);
+        assert_eq!(Frac::from(0).valuation(5), None);
+        assert_eq!(x.padic_abs(5), Some(Frac::new(1, 25)));
+        assert_eq!(x.padic_abs(3), Some(Frac::from(3)));
+        assert_eq!(Frac::from(0).padic_abs(3), Some(Frac::from(0)));
+        assert_eq!(x.padic_abs(0), None);
+        assert_eq!(int_valuation(-24, 2), Some(3));
+        assert_eq!(int_valuation(24, 0), None);
+    }
+
+    #[test]
+    fn padic_digits() {
+        // -1 = ...4444 and 1/3 = ...13132 in the 5-adics
+        let minus_one = Frac::from(-1).padic_expansion(5, 4).unwrap();
+        assert_eq!(minus_one.digits, vec![4, 4, 4, 4]);
+        assert_eq!(minus_one.to_string(), "…4444 (5-adic)");
+        assert_eq!(Frac::new(1, 3).padic_expansion(5, 5).unwrap().digits, vec![2, 3, 1, 3, 1]);
+        assert_eq!(Frac::from(50).padic_expansion(5, 3).unwrap().to_string(), "…00200 (5-adic)");
+        assert!(Frac::from(2).padic_expansion(6, 3).is_err());
+        assert!(Frac::from(0).padic_expansion(5, 3).is_err());
+    }
+
+    #[test]
+    fn hensel_lifting() {
+        // x^2 = 2 has the roots 3 and 4 mod 7, and each lifts to one root mod 7^3
+        let coeffs = [-2, 0, 1];
+        let roots = solve_congruence(&coeffs, 7, 3).unwrap();
+        assert_eq!(roots.len(), 2);
+        for &r in roots.iter() {
+            assert_eq!((r * r - 2) % 343, 0);
+        }
+        assert_eq!(roots.iter().map(|r| r % 7).collect::<Vec<i64>>(), vec![3, 4]);
+        assert_eq!(hensel_lift(&coeffs, 3, 7, 1), Ok(3));
+        // 0 is a double root of x^2, so there's no unique lift
+        assert!(hensel_lift(&[0, 0, 1], 0, 5, 2).is_err());
+        assert!(hensel_lift(&coeffs, 1, 7, 2).is_err());
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(2, 4), None);
+    }
+
+    #[test]
+    fn padic_point_with_short_expansion() {
+        let small = Frac::new(1, 125).padic_expansion(5, 2).unwrap();
+        assert_eq!(small.valuation, -3);
+        assert_eq!(small.digits, vec![1, 0, 0, 0]);
+        assert_eq!(small.to_string(), "…0.001 (5-adic)");
+        assert_eq!(Frac::new(26, 125).padic_expansion(5, 2).unwrap().to_string(), "…0.101 (5-adic)");
+        // Hand-built ones get padded instead
+        let short = PAdic { p: 5, valuation: -3, digits: vec![1] };
+        assert_eq!(short.to_string(), "…0.001 (5-adic)");
+    }
+}
@@ -1,8 +1,11 @@
 #![allow(dead_code)]
 
 mod fracs;
-use fracs::*;
+mod field;
 mod mats;
+mod trig;
+mod swap;
+mod poly;
 use mats::*;
 
 fn main() {
@@ -50,7 +53,7 @@ fn main() {
     let m1_res = Matrix::from_i32_vec(5, v1, TSOpts{try: false, print: false});
     let mut m1 = match m1_res {
         Ok(matr) => matr,
-        Err(e) => panic!(format!("Error! {}", e))
+        Err(e) => panic!("Error! {}", e)
     };
-    m1.reduced_row_echelon_form(true);
+    m1.reduced_row_echelon_form(&mut format::PrintSink);
 }
\ No newline at end of file
@@ -1,69 +1,56 @@
 #![allow(dead_code)]
 
+use std::convert::TryFrom;
+use std::error;
 use std::fmt;
-use std::cmp;
+use std::str::FromStr;
 
 use fracs;
+use field::Field;
 
+// Entries can be anything implementing field::Field. The default is fracs::Frac, which is what the
+// simplification helpers and most of the step printing were written around.
+//
+// Storage is a single row-major buffer. Rows are looked up through `row_order`, so logical row i
+// lives at physical row `row_order[i]` - swapping two rows only swaps two indices.
 #[derive(Clone)]
-pub struct Matrix {
-    pub dimension: (usize, usize),
-    pub matrix: Vec<Vec<fracs::Frac>>
+pub struct Matrix<T: Field = fracs::Frac> {
+    height: usize,
+    width: usize,
+    data: Vec<T>,
+    row_order: Vec<usize>
 }
 
-impl fmt::Display for Matrix {
+impl<T: Field> fmt::Display for Matrix<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let matr = self.to_string();
-        write!(f, "{}", matr)
-    }
-}
-
-pub struct TSOpts {
-    pub try: bool,
-    pub print: bool
-}
-
-impl From<(bool, bool)> for TSOpts {
-    fn from(tup: (bool, bool)) -> Self {
-        TSOpts {
-            try: tup.0,
-            print: tup.1
-        }
-    }
-}
-
-impl Matrix {
-    pub fn to_string(&self) -> String {
         let mut matr = String::from(""); // Will contain string for entire matrix
-        let mut longest_in_column: Vec<usize> = Vec::with_capacity(self.dimension.1);
-        for _ in 0..self.dimension.1 {
-            longest_in_column.push(0);
-        }
-        for a in 0..self.dimension.0 {
-            for b in 0..self.dimension.1 {
-                if self.matrix[a][b].as_string().len() > longest_in_column[b] {
-                    longest_in_column[b] = self.matrix[a][b].as_string().len();
+        let mut longest_in_column: Vec<usize> = vec![0; self.width];
+        for a in 0..self.height {
+            for (longest, elem) in longest_in_column.iter_mut().zip(self.row_slice(a).iter()) {
+                let len = format!("{}", elem).len();
+                if len > *longest {
+                    *longest = len;
                 }
             }
         }
-        for a in 0..self.dimension.0 {
+        for a in 0..self.height {
             let mut line = String::from(""); // String for each individual line
             // Add the appropriate character for the section of the bracket at the start of each line
             if a == 0 {
                 line = format!("⎡ {}", line);
-            } else if a == self.dimension.0 - 1 {
+            } else if a == self.height - 1 {
                 line = format!("⎣ {}", line);
             } else {
                 line = format!("⎢ {}", line);
             }
             // Add spacing to line up the right side of the numbers in each column
-            for b in 0..self.dimension.1 {
+            for (b, longest) in longest_in_column.iter().enumerate() {
                 let mut spacer_left = String::from("");
-                let elem_string = self.matrix[a][b].as_string();
-                for _ in 0..longest_in_column[b] - elem_string.len() {
+                let elem_string = format!("{}", self.at(a, b));
+                for _ in 0..longest - elem_string.len() {
                     spacer_left = format!("{}{}", spacer_left, " ");
                 }
-                if b == self.dimension.1 - 1 {
+                if b == self.width - 1 {
                     line = format!("{}{}{}", line, spacer_left, elem_string);
                 } else {
                     line = format!("{}{}{}, ", line, spacer_left, elem_string);
@@ -72,702 +59,3812 @@ impl Matrix {
             // Append appropriate end symbol for bracket section at the end of each line
             if a == 0 {
                 line = format!("{} ⎤", line);
-            } else if a == self.dimension.0 - 1 {
+            } else if a == self.height - 1 {
                 line = format!("{} ⎦", line);
             } else {
                 line = format!("{} ⎥", line);
             }
             // Add line to matrix string, add newline if it's not the last line
-            if a == self.dimension.0 - 1 {
+            if a == self.height - 1 {
                 matr = format!("{}{}", matr, line);
             } else {
                 matr = format!("{}{}\n", matr, line);
             }
         }
-        matr
+        write!(f, "{}", matr)
     }
+}
 
-    pub fn from_dimension(dim: (usize, usize)) -> Self {
-        let mut mat: Vec<Vec<fracs::Frac>> = Vec::with_capacity(dim.0);
-        for _ in 0..dim.0 {
-            let mut row: Vec<fracs::Frac> = Vec::with_capacity(dim.1);
-            for _ in 0..dim.1 {
-                row.push(fracs::Frac::from(0));
+// Shows the grid, same as Display, rather than the flat buffer and row order
+impl<T: Field> fmt::Debug for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Matrix {}x{}\n{}", self.height, self.width, self)
+    }
+}
+
+// Compares entries in logical order, so two matrices that differ only in how their rows are stored
+// are equal
+impl<T: Field> PartialEq for Matrix<T> {
+    fn eq(&self, other: &Matrix<T>) -> bool {
+        self.dimension() == other.dimension() && (0..self.height).all(|a| self.row_slice(a) == other.row_slice(a))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MatrixError {
+    // `op` names the operation, e.g. "addition"
+    DimensionMismatch { left: (usize, usize), right: (usize, usize), op: &'static str },
+    NotSquare { dimension: (usize, usize) },
+    // Entry (row, col) doesn't match (col, row)
+    NotSymmetric { row: usize, col: usize },
+    // No pivot could be found in this column, so the matrix has no inverse
+    Singular { pivot_col: usize },
+    RaggedRows { row: usize, expected: usize, found: usize },
+    // An entry doesn't fit in the narrower entry type being converted to
+    Overflow { row: usize, col: usize },
+    Empty,
+    Parse { row: usize, col: usize, token: String },
+    // A matrix equation has no solution for this right-hand side (column of B in AX = B, row of B
+    // in XA = B)
+    NoSolution { rhs: usize },
+    // A matrix equation has more than one solution. These are the unknowns left free: columns of A
+    // in AX = B, rows of A in XA = B.
+    NotUnique { free_vars: Vec<usize> },
+    // There's no row or column `index` - `axis` says which, and `len` is how many there are
+    OutOfBounds { axis: &'static str, index: usize, len: usize },
+    // The λ^power coefficient of a characteristic polynomial doesn't fit in the entry type
+    CoefficientOverflow { power: usize }
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MatrixError::DimensionMismatch { left, right, op } => {
+                write!(f, "Matrices have incompatible dimensions for {}: {}x{} and {}x{}.", op, left.0, left.1, right.0, right.1)
+            },
+            MatrixError::NotSquare { dimension } => {
+                write!(f, "Matrix must be square, but it's {}x{}.", dimension.0, dimension.1)
+            },
+            MatrixError::NotSymmetric { row, col } => {
+                write!(f, "Matrix must be symmetric, but entries ({0}, {1}) and ({1}, {0}) differ.", row + 1, col + 1)
+            },
+            MatrixError::Singular { pivot_col } => {
+                write!(f, "Matrix is singular - there's no pivot in column {}.", pivot_col + 1)
+            },
+            MatrixError::RaggedRows { row, expected, found } => {
+                write!(f, "Row {} has {} entries, but the rows before it have {}.", row + 1, found, expected)
+            },
+            MatrixError::Overflow { row, col } => {
+                write!(f, "Entry ({}, {}) is too large for the target type.", row + 1, col + 1)
+            },
+            MatrixError::Empty => write!(f, "Matrix has no entries."),
+            MatrixError::Parse { row, col, ref token } => {
+                write!(f, "Couldn't parse \"{}\" at ({}, {}).", token, row + 1, col + 1)
+            },
+            MatrixError::NoSolution { rhs } => write!(f, "There's no solution for right-hand side {}.", rhs + 1),
+            MatrixError::NotUnique { ref free_vars } => {
+                let free = free_vars.iter().map(|var| (var + 1).to_string()).collect::<Vec<String>>().join(", ");
+                write!(f, "There's no unique solution - unknowns {} are free.", free)
+            },
+            MatrixError::OutOfBounds { axis, index, len } => {
+                write!(f, "There's no {} {} - the matrix only has {} {0}s.", axis, index + 1, len)
+            },
+            MatrixError::CoefficientOverflow { power } => {
+                write!(f, "The λ^{} coefficient is too large for the entry type.", power)
             }
-            mat.push(row);
         }
+    }
+}
+
+impl error::Error for MatrixError {}
+
+// Whether constructors should try to simplify each row, and whether to print the steps if so
+#[derive(Clone, Copy, Default)]
+pub struct TSOpts {
+    pub try: bool,
+    pub print: bool
+}
+
+impl From<(bool, bool)> for TSOpts {
+    fn from(tup: (bool, bool)) -> Self {
+        TSOpts {
+            try: tup.0,
+            print: tup.1
+        }
+    }
+}
+
+impl<T: Field> Matrix<T> {
+    pub fn from_dimension(dim: (usize, usize)) -> Self {
         Matrix {
-            dimension: dim,
-            matrix: mat
+            height: dim.0,
+            width: dim.1,
+            data: vec![T::zero(); dim.0 * dim.1],
+            row_order: (0..dim.0).collect()
         }
     }
 
-    pub fn from_vecs(vecs: Vec<Vec<fracs::Frac>>, try_simplify_opts: TSOpts) -> Result<Matrix, String> {
-        for a in 0..vecs.len() - 1 {
-            for b in a..vecs.len() {
-                if vecs[a].len() != vecs[b].len() {
-                    return Err(String::from("One or more vecs in input vec had unequal length."));
-                }
-            }
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix::from_dimension((rows, cols))
+    }
+
+    pub fn ones(rows: usize, cols: usize) -> Self {
+        Matrix::scalar_fill(rows, cols, T::one())
+    }
+
+    pub fn identity(n: usize) -> Self {
+        Matrix::scalar(n, T::one())
+    }
+
+    // k * I
+    pub fn scalar(n: usize, k: T) -> Self {
+        let mut ret = Matrix::from_dimension((n, n));
+        for a in 0..n {
+            *ret.at_mut(a, a) = k;
+        }
+        ret
+    }
+
+    pub fn diag(entries: &[T]) -> Self {
+        let mut ret = Matrix::from_dimension((entries.len(), entries.len()));
+        for (a, &elem) in entries.iter().enumerate() {
+            *ret.at_mut(a, a) = elem;
+        }
+        ret
+    }
+
+    fn scalar_fill(rows: usize, cols: usize, k: T) -> Self {
+        Matrix {
+            height: rows,
+            width: cols,
+            data: vec![k; rows * cols],
+            row_order: (0..rows).collect()
         }
-        let mut ret = Matrix {
-            dimension: (vecs.len(), vecs[0].len()),
-            matrix: vecs
+    }
+
+    // Quiet counterpart to from_vecs that works for any entry type
+    pub fn from_rows(vecs: Vec<Vec<T>>) -> Result<Matrix<T>, MatrixError> {
+        let width = match vecs.first() {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => return Err(MatrixError::Empty)
         };
-        print!("Constructed matrix:\n{}\n\n", ret);
-        if try_simplify_opts.try {
-            ret.try_simplify_matrix(try_simplify_opts.print);
+        if let Some((row, ragged)) = vecs.iter().enumerate().find(|&(_, row)| row.len() != width) {
+            return Err(MatrixError::RaggedRows { row, expected: width, found: ragged.len() });
         }
-        Ok(ret)
+        let height = vecs.len();
+        Ok(Matrix {
+            height,
+            width,
+            data: vecs.into_iter().flatten().collect(),
+            row_order: (0..height).collect()
+        })
     }
 
-    pub fn from_i32_vec(width: usize, vec: Vec<i32>, try_simplify_opts: TSOpts) -> Result<Matrix, String> {
-        if vec.len() % width != 0 {
-            return Err(String::from(
-                format!("Input vec len ({}) is not divisible by desired matrix width ({}).", vec.len(), width)
-            ));
+    // Row-major entries, `width` to a row
+    pub fn from_flat(width: usize, vec: Vec<T>) -> Result<Matrix<T>, MatrixError> {
+        if width == 0 || vec.is_empty() {
+            return Err(MatrixError::Empty);
         }
-        let mut matr: Vec<Vec<fracs::Frac>> = Vec::with_capacity(vec.len() / width);
-        let mut ct = 0;
-        for _ in 0..vec.len() / width {
-            let mut new: Vec<fracs::Frac> = Vec::with_capacity(width);
-            for _ in 0..width {
-                new.push(fracs::Frac::from(vec[ct]));
-                ct += 1;
-            }
-            matr.push(new);
+        if !vec.len().is_multiple_of(width) {
+            // The last row is the short one
+            return Err(MatrixError::RaggedRows { row: vec.len() / width, expected: width, found: vec.len() % width });
         }
-        let mut ret = Matrix {
-            dimension: (vec.len() / width, width),
-            matrix: matr
-        };
-        print!("Constructed matrix:\n\n{}\n\n\n", ret);
-        if try_simplify_opts.try {
-            ret.try_simplify_matrix(try_simplify_opts.print);
+        Ok(Matrix {
+            height: vec.len() / width,
+            width,
+            row_order: (0..vec.len() / width).collect(),
+            data: vec
+        })
+    }
+
+    // (rows, columns)
+    pub fn dimension(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn to_vecs(&self) -> Vec<Vec<T>> {
+        (0..self.height).map(|a| self.row_slice(a).to_vec()).collect()
+    }
+
+    // Row-major copy of the entries in logical order
+    pub fn to_flat(&self) -> Vec<T> {
+        (0..self.height).flat_map(|a| self.row_slice(a).iter().cloned()).collect()
+    }
+
+    // Physically reorders the buffer so the rows are stored in their logical order again
+    pub fn compact(&mut self) {
+        if self.row_order.iter().enumerate().all(|(a, &p)| a == p) {
+            return;
         }
-        Ok(ret)
+        self.data = self.to_flat();
+        self.row_order = (0..self.height).collect();
     }
-}
 
-pub mod format {
-    use std::fmt;
-    use mats::Matrix;
+    // O(1): only the row indices move
+    pub fn swap_rows(&mut self, row1: usize, row2: usize) {
+        self.row_order.swap(row1, row2);
+    }
 
-    pub enum Separator {
-        Plus,
-        Minus,
-        Times,
-        Divide,
-        Space
+    fn pos(&self, row: usize, col: usize) -> usize {
+        self.row_order[row] * self.width + col
     }
 
-    impl fmt::Display for Separator {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            match self {
-                &Separator::Plus => write!(f, "+"),
-                &Separator::Minus => write!(f, "-"),
-                &Separator::Times => write!(f, "*"),
-                &Separator::Divide => write!(f, "/"),
-                &Separator::Space => write!(f, " ")
-            }
+    fn at(&self, row: usize, col: usize) -> T {
+        self.data[self.pos(row, col)]
+    }
+
+    fn at_mut(&mut self, row: usize, col: usize) -> &mut T {
+        let pos = self.pos(row, col);
+        &mut self.data[pos]
+    }
+
+    fn row_slice(&self, row: usize) -> &[T] {
+        let start = self.row_order[row] * self.width;
+        &self.data[start..start + self.width]
+    }
+
+    fn row_slice_mut(&mut self, row: usize) -> &mut [T] {
+        let start = self.row_order[row] * self.width;
+        &mut self.data[start..start + self.width]
+    }
+
+    // A mutable row alongside a different, read-only one, for row operations like R1 - R2 → R1
+    fn row_pair_mut(&mut self, target: usize, tool: usize) -> (&mut [T], &[T]) {
+        let (pt, ps, w) = (self.row_order[target], self.row_order[tool], self.width);
+        assert!(pt != ps, "row_pair_mut needs two different rows");
+        if pt < ps {
+            let (lo, hi) = self.data.split_at_mut(ps * w);
+            (&mut lo[pt * w..(pt + 1) * w], &hi[..w])
+        } else {
+            let (lo, hi) = self.data.split_at_mut(pt * w);
+            (&mut hi[..w], &lo[ps * w..(ps + 1) * w])
         }
     }
+}
+
+impl Matrix {
+    pub fn from_vecs(vecs: Vec<Vec<fracs::Frac>>, try_simplify_opts: TSOpts) -> Result<Matrix, MatrixError> {
+        let mut ret = Matrix::from_rows(vecs)?;
+        ret.apply_ts_opts(try_simplify_opts);
+        Ok(ret)
+    }
+
+    pub fn from_i32_vec(width: usize, vec: Vec<i32>, try_simplify_opts: TSOpts) -> Result<Matrix, MatrixError> {
+        let mut ret = Matrix::from_flat(width, vec.into_iter().map(fracs::Frac::from).collect())?;
+        ret.apply_ts_opts(try_simplify_opts);
+        Ok(ret)
+    }
 
-    pub fn add_mat_to_string(string: String, matr: &Matrix, separator: Separator) -> String {
-        let mut lines_vec = string.lines().map(|line| String::from(line)).collect::<Vec<String>>();
-        let mut mat_vec = matr.to_string().lines().map(|line| String::from(line)).collect::<Vec<String>>();
-        let (mut top_gap, mut height_comp_state) = (0, -2);
-        if lines_vec.len() > mat_vec.len() {
-            height_comp_state = -1;
-        } else if lines_vec.len() == mat_vec.len() {
-            height_comp_state = 0;
+    // Constructors stay quiet unless asked to print their simplification steps. For any other
+    // destination, build the matrix first and call try_simplify_matrix with a sink.
+    fn apply_ts_opts(&mut self, try_simplify_opts: TSOpts) {
+        if !try_simplify_opts.try {
+            return;
+        }
+        if try_simplify_opts.print {
+            self.try_simplify_matrix(&mut format::PrintSink);
         } else {
-            height_comp_state = 1;
+            self.try_simplify_matrix(&mut format::Silent);
         }
-        if height_comp_state == 0 {
-            for i in 0..lines_vec.len() {
-                if i == lines_vec.len() / 2 {
-                    lines_vec[i] = format!("{} {} {}", lines_vec[i], separator, mat_vec[i]);
-                } else {
-                    lines_vec[i] = format!("{}   {}", lines_vec[i], mat_vec[i]);
+    }
+}
+
+// Parses rows separated by newlines or semicolons, with entries separated by commas. Bracket
+// characters are ignored, so the output of Display parses back into the same matrix.
+impl<T: Field + FromStr> FromStr for Matrix<T> {
+    type Err = MatrixError;
+
+    fn from_str(string: &str) -> Result<Matrix<T>, MatrixError> {
+        let cleaned = string.replace(|c: char| "⎡⎢⎣⎤⎥⎦[]".contains(c), "");
+        let mut rows = Vec::new();
+        for (a, line) in cleaned.split(['\n', ';']).map(str::trim).filter(|line| !line.is_empty()).enumerate() {
+            let mut row = Vec::new();
+            for (b, token) in line.split(',').map(str::trim).enumerate() {
+                match token.parse::<T>() {
+                    Ok(elem) => row.push(elem),
+                    Err(_) => return Err(MatrixError::Parse { row: a, col: b, token: String::from(token) })
                 }
             }
-            let mut ret = String::from("");
-            for i in 0..lines_vec.len() - 1 {
-                ret = format!("{}{}\n", ret, lines_vec[i]);
-            }
-            ret = format!("{}{}", ret, lines_vec[lines_vec.len() - 1]);
-            return ret;
+            rows.push(row);
         }
-        let mut ws_max = 0;
-        let mut max = 0;
-        if height_comp_state == -1 {
-            ws_max = lines_vec.len() - mat_vec.len();
-            max = lines_vec.len();
-        } else {
-            ws_max = mat_vec.len() - lines_vec.len();
-            max = mat_vec.len();
-        }
-        for a in (0..ws_max).filter(|&a| a & 1 == 0) {
-            top_gap += 1;
-        }
-        let mut new_lines: Vec<String> = Vec::new();
-        match height_comp_state > 0 {
-            true => {
-                for i in top_gap..max {
-                    if i == mat_vec.len() / 2 {
-                        mat_vec[i] = format!("{} {} {}", mat_vec[i], separator, lines_vec[i - top_gap]);
-                    } else {
-                        mat_vec[i] = format!("{}   {}", mat_vec[i], lines_vec[i - top_gap]);
-                    }
-                }
-                new_lines = mat_vec;
-            },
-            false => {
-                for i in top_gap..max {
-                    if i == lines_vec.len() / 2 {
-                        lines_vec[i] = format!("{} {} {}", lines_vec[i], separator, mat_vec[i - top_gap]);
-                    } else {
-                        lines_vec[i] = format!("{}   {}", lines_vec[i], mat_vec[i - top_gap]);
-                    }
+        Matrix::from_rows(rows)
+    }
+}
+
+impl Matrix<fracs::WideFrac> {
+    // Narrows back down to 32 bit fractions, failing on the first entry that doesn't fit
+    pub fn to_frac_matrix(&self) -> Result<Matrix, MatrixError> {
+        let mut rows = Vec::with_capacity(self.height);
+        for a in 0..self.height {
+            let mut row = Vec::with_capacity(self.width);
+            for b in 0..self.width {
+                match fracs::Frac::try_from(self.at(a, b)) {
+                    Ok(frac) => row.push(frac),
+                    Err(_) => return Err(MatrixError::Overflow { row: a, col: b })
                 }
-                new_lines = lines_vec;
             }
+            rows.push(row);
         }
-        let mut ret = String::from("");
-        for i in 0..new_lines.len() - 1 {
-            ret = format!("{}{}\n", ret, new_lines[i]);
-        }
-        ret = format!("{}{}", ret, new_lines[new_lines.len() - 1]);
-        ret
+        Matrix::from_rows(rows)
     }
 }
 
-pub mod operations {
-    use fracs;
+// Borrowed windows onto a matrix: submatrices, single rows and columns, and transposes, all without
+// copying any entries. Coordinates are always relative to the view.
+pub mod views {
+    use std::fmt;
+    use field::Field;
     use mats::Matrix;
-    use mats::format::*;
 
-    impl Matrix {
-        pub fn add(&mut self, other: Matrix, print_action: bool) -> Result<&mut Matrix, String> {
-            if self.dimension.0 != other.dimension.0 || self.dimension.1 != other.dimension.1 {
-                return Err(String::from("Matrices are not of the same dimension - unable to perform addition."));
-            }
-            if print_action {
-                println!("{}\n", add_mat_to_string(self.to_string(), &other, Separator::Plus));
-            }
-            for i in 0..self.dimension.0 {
-                for j in 0..self.dimension.1 {
-                    self.matrix[i][j] = self.matrix[i][j].add(other.matrix[i][j]);
-                }
+    // The window is described in the coordinates of the underlying buffer (before transposing), so
+    // entry (i, j) of an untransposed view lives at
+    // data[row_order[row_start + i] * stride + col_start + j].
+    #[derive(Clone, Copy)]
+    struct Window {
+        stride: usize,
+        row_start: usize,
+        col_start: usize,
+        height: usize,
+        width: usize,
+        transposed: bool
+    }
+
+    impl Window {
+        fn dimension(&self) -> (usize, usize) {
+            if self.transposed {
+                (self.width, self.height)
+            } else {
+                (self.height, self.width)
             }
-            Ok(self)
         }
 
-        pub fn sub(&mut self, other: Matrix, print_action: bool) -> Result<&mut Matrix, String> {
-            if self.dimension.0 != other.dimension.0 || self.dimension.1 != other.dimension.1 {
-                return Err(String::from("Matrices are not of the same dimension - unable to perform subtraction."));
-            }
-            if print_action {
-                println!("{}\n", add_mat_to_string(self.to_string(), &other, Separator::Minus));
-            }
-            for i in 0..self.dimension.0 {
-                for j in 0..self.dimension.1 {
-                    self.matrix[i][j] = self.matrix[i][j].sub(other.matrix[i][j]);
-                }
-            }
-            Ok(self)
+        fn pos(&self, row_order: &[usize], row: usize, col: usize) -> usize {
+            let (dim_rows, dim_cols) = self.dimension();
+            assert!(row < dim_rows && col < dim_cols, "({}, {}) is outside of a {}x{} view", row, col, dim_rows, dim_cols);
+            let (r, c) = if self.transposed { (col, row) } else { (row, col) };
+            row_order[self.row_start + r] * self.stride + self.col_start + c
         }
 
-        pub fn mul(&mut self, other: Matrix, print_action: bool) -> Result<Matrix, String> {
-            if self.dimension.1 != other.dimension.0 {
-                return Err(String::from("Matrices do not have matching b, c dimensions for a, b x c, d."));
-            }
-            if print_action {
-                println!("{}\n", add_mat_to_string(self.to_string(), &other, Separator::Times));
-            }
-            let mut ret = Matrix::from_dimension((self.dimension.0, other.dimension.1));
-            for a in 0..self.dimension.0 {
-                for o in 0..other.dimension.1 {
-                    let mut total = fracs::Frac::from(0);
-                    let other_column = (0..other.dimension.0).map(|i| other.matrix[i][o]).collect::<Vec<fracs::Frac>>();
-                    for b in 0..self.dimension.1 {
-                        let new = self.matrix[a][b].mul(other_column[b]);
-                        total = total.add(new);
-                    }
-                    ret.matrix[a][o] = total;
-                }
+        // Window for the rows..rows + height, cols..cols + width block of this (possibly
+        // transposed) window
+        fn sub(&self, rows: (usize, usize), cols: (usize, usize)) -> Window {
+            let (dim_rows, dim_cols) = self.dimension();
+            assert!(rows.0 + rows.1 <= dim_rows && cols.0 + cols.1 <= dim_cols,
+                    "Submatrix doesn't fit in a {}x{} view", dim_rows, dim_cols);
+            let (r, c) = if self.transposed { (cols, rows) } else { (rows, cols) };
+            Window {
+                stride: self.stride,
+                row_start: self.row_start + r.0,
+                col_start: self.col_start + c.0,
+                height: r.1,
+                width: c.1,
+                transposed: self.transposed
             }
-            Ok(ret)
         }
 
-        // "Divide" by multiplying by the inverse of the other matrix
-        pub fn div(&mut self, other: Matrix, print_action: bool, print_inverse_steps: bool) -> Result<Matrix, String> {
-            if !(self.dimension.1 == other.dimension.0 && other.dimension.0 == other.dimension.1) {
-                return Err(String::from("Unable to do division with these two matrices. The divisor must be a square matrix,\
-            and the dividend's number of columns must be the same as that of both dimensions in the divisor."));
-            }
-            if print_action {
-                println!("{}\n", add_mat_to_string(self.to_string(), &other, Separator::Divide));
-            }
-            let rehto = other.inverse(print_inverse_steps);
-            match rehto {
-                Err(e) => Err(e),
-                Ok(xirtam) => {
-                    if print_action {
-                        println!("{}\n", add_mat_to_string(self.to_string(), &xirtam, Separator::Times));
-                    }
-                    let res = self.mul(xirtam, false);
-                    match res {
-                        Err(e) => Err(e),
-                        Ok(result) => Ok(result)
-                    }
-                }
+        fn transposed(&self) -> Window {
+            Window {
+                transposed: !self.transposed,
+                ..*self
             }
         }
     }
-}
 
-pub mod transforms {
-    use std::cmp;
-    use fracs;
-    use mats::Matrix;
-    use mats::format::*;
+    #[derive(Clone, Copy)]
+    pub struct MatrixView<'a, T: Field + 'a> {
+        data: &'a [T],
+        row_order: &'a [usize],
+        window: Window
+    }
 
-    enum RowOps {
-        Add((usize, usize)),
-        Sub((usize, usize)),
-        Mul((usize, fracs::Frac)),
-        Div((usize, fracs::Frac)),
-        SwapRows((usize, usize)),
-        TrySimplify((usize, bool))
+    pub struct MatrixViewMut<'a, T: Field + 'a> {
+        data: &'a mut [T],
+        row_order: &'a [usize],
+        window: Window
     }
 
-    impl Matrix {
-        fn row_op(&mut self, op: RowOps) {
-            match op {
-                RowOps::Add(tup) => {
-                    for b in 0..self.dimension.1 {
-                        self.matrix[tup.0][b] = self.matrix[tup.0][b].add(self.matrix[tup.1][b]);
-                    }
-                },
-                RowOps::Sub(tup) => {
-                    for b in 0..self.dimension.1 {
-                        self.matrix[tup.0][b] = self.matrix[tup.0][b].sub(self.matrix[tup.1][b]);
-                    }
-                },
-                RowOps::Mul(tup) => {
-                    for b in 0..self.dimension.1 {
-                        self.matrix[tup.0][b] = self.matrix[tup.0][b].mul(tup.1);
-                    }
-                },
-                RowOps::Div(tup) => {
-                    for b in 0..self.dimension.1 {
-                        self.matrix[tup.0][b] = self.matrix[tup.0][b].div(tup.1);
-                    }
-                },
-                RowOps::SwapRows(tup) => {
-                    self.matrix.swap(tup.0, tup.1);
-                },
-                RowOps::TrySimplify(tup) => {
-                    self.try_simplify(tup.0, tup.1);
-                }
+    impl<'a, T: Field> MatrixView<'a, T> {
+        pub fn dimension(&self) -> (usize, usize) {
+            self.window.dimension()
+        }
+
+        pub fn get(&self, row: usize, col: usize) -> T {
+            self.data[self.window.pos(self.row_order, row, col)]
+        }
+
+        pub fn submatrix(&self, row: usize, col: usize, dim: (usize, usize)) -> MatrixView<'a, T> {
+            MatrixView {
+                window: self.window.sub((row, dim.0), (col, dim.1)),
+                ..*self
             }
         }
 
-        pub fn try_simplify_matrix(&mut self, print_steps: bool) {
-            println!("Attempting to simplify matrix.\n");
-            let mut did_simplification = false;
-            for row in 0..self.matrix.len() {
-                match self.try_simplify(row, print_steps) {
-                    true => did_simplification = true,
-                    _ => {}
-                }
+        pub fn row(&self, row: usize) -> MatrixView<'a, T> {
+            self.submatrix(row, 0, (1, self.dimension().1))
+        }
+
+        pub fn col(&self, col: usize) -> MatrixView<'a, T> {
+            self.submatrix(0, col, (self.dimension().0, 1))
+        }
+
+        pub fn transpose(&self) -> MatrixView<'a, T> {
+            MatrixView {
+                window: self.window.transposed(),
+                ..*self
             }
-            if did_simplification {
-                println!("\nWas able to simplify. New matrix:\n\n{}\n", self);
-            }
-            if !did_simplification {
-                println!("\nWas unable to simplify.\n");
-            }
-        }
-
-        pub fn try_simplify(&mut self, row: usize, print_steps: bool) -> bool {
-            if self.matrix[row].len() > 1 {
-                let row_vec: &Vec<fracs::Frac> = &self.clone().matrix[row];
-                let (mut zero_count, mut plus_minus_one_count, mut neg_count, mut non_zeros) = (0, 0, 0, Vec::new());
-                for i in 0..row_vec.len() {
-                    let tst = row_vec[i];
-                    match tst.cmp(&fracs::Frac::from(0)) {
-                        fracs::CmpRes::Eq => zero_count += 1,
-                        fracs::CmpRes::Lt => {
-                            neg_count += 1;
-                            match tst.eq(&fracs::Frac::from(-1)) {
-                                true => plus_minus_one_count += 1,
-                                false => non_zeros.push(tst)
-                            }
-                        }
-                        fracs::CmpRes::Gt => {
-                            match tst.eq(&fracs::Frac::from(1)) {
-                                true => plus_minus_one_count += 1,
-                                false => non_zeros.push(tst)
-                            }
-                        }
-                    }
-                }
-                if neg_count == self.matrix[row].len() {
-                    for b in 0..neg_count {
-                        self.matrix[row][b] = self.matrix[row][b].negative();
-                    }
-                    if print_steps {
-                        print!("(-1) * R{} → R{0}\n{}\n\n", row, self);
-                    }
-                }
-                if non_zeros.len() > 1 {
-                    if plus_minus_one_count == 0 {
-                        let first_frac = non_zeros[0];
-                        let second_frac = non_zeros[1];
-                        let mut num_gcd = fracs::get_gcd(first_frac.num as u32, second_frac.num as u32);
-                        let mut den_gcd = fracs::get_gcd(first_frac.den as u32, second_frac.den as u32);
-                        if num_gcd == 1 && den_gcd == 1 {
-                            return false;
-                        }
-                        if non_zeros.len() > 2 {
-                            for i in 2..non_zeros.len() {
-                                let next = non_zeros[i];
-                                num_gcd = fracs::get_gcd(num_gcd, next.num as u32);
-                                den_gcd = fracs::get_gcd(den_gcd, next.den as u32);
-                                if num_gcd == 1 && den_gcd == 1 {
-                                    return false;
-                                }
-                            }
-                        }
-                        let sorta_gcd = fracs::Frac::new(num_gcd as i32, den_gcd as i32);
-                        for b in 0..row_vec.len() {
-                            self.matrix[row][b] = self.matrix[row][b].div(sorta_gcd);
-                        }
-                        if print_steps {
-                            print!("({}) * R{} → R{1}\n{}\n\n", sorta_gcd.inverse(), row, self);
-                        }
-                        return true;
-                    }
+        }
+
+        pub fn to_matrix(self) -> Matrix<T> {
+            let (rows, cols) = self.dimension();
+            let mut data = Vec::with_capacity(rows * cols);
+            for a in 0..rows {
+                for b in 0..cols {
+                    data.push(self.get(a, b));
                 }
             }
-            false
+            Matrix {
+                height: rows,
+                width: cols,
+                data,
+                row_order: (0..rows).collect()
+            }
         }
+    }
 
-        // Wrapper functions for convenience
-        pub fn row_ops_add(&mut self, target_row: usize, tool: usize) {
-            self.row_op(RowOps::Add((target_row, tool)));
+    impl<'a, T: Field> fmt::Display for MatrixView<'a, T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.to_matrix())
         }
+    }
 
-        pub fn row_ops_sub(&mut self, target_row: usize, tool: usize) {
-            self.row_op(RowOps::Sub((target_row, tool)));
+    impl<'a, T: Field> MatrixViewMut<'a, T> {
+        pub fn dimension(&self) -> (usize, usize) {
+            self.window.dimension()
         }
 
-        pub fn row_ops_mul(&mut self, target_row: usize, amt: fracs::Frac) {
-            self.row_op(RowOps::Mul((target_row, amt)));
+        pub fn get(&self, row: usize, col: usize) -> T {
+            self.data[self.window.pos(self.row_order, row, col)]
         }
 
-        pub fn row_ops_div(&mut self, target_row: usize, amt: fracs::Frac) {
-            self.row_op(RowOps::Div((target_row, amt)));
+        pub fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+            let pos = self.window.pos(self.row_order, row, col);
+            &mut self.data[pos]
         }
 
-        pub fn row_ops_swap(&mut self, row1: usize, row2: usize) {
-            self.row_op(RowOps::SwapRows((row1, row2)));
+        pub fn set(&mut self, row: usize, col: usize, val: T) {
+            *self.get_mut(row, col) = val;
         }
 
-        pub fn row_ops_try_simplify(&mut self, row: usize, print_steps: bool) {
-            self.row_op(RowOps::TrySimplify((row, print_steps)));
+        pub fn fill(&mut self, val: T) {
+            let (rows, cols) = self.dimension();
+            for a in 0..rows {
+                for b in 0..cols {
+                    self.set(a, b, val);
+                }
+            }
         }
 
-        pub fn row_echelon_form(&mut self, print_steps: bool) {
-            if print_steps {
-                println!("------- Starting REF -------\n");
-            }
-            let max = cmp::min(self.dimension.0, self.dimension.1);
-            for a in 0..max {
-                for b in 0..a + 1 { // Keep tested values "below" or on the diagonal line
-                    let amt1 = self.clone().matrix[a][b]; // Current value
-                    if b < a { // "Under" the diagonal line
-                        if amt1.num == 0 {
-                            continue;
-                        }
-                        let mut sign = String::from("");
-                        let mut neg = false;
-                        match amt1.num > 0 {
-                            true => {
-                                self.row_ops_mul(b, amt1);
-                                self.row_ops_sub(a, b);
-                                self.row_ops_div(b, amt1);
-                                sign = String::from("-");
-                            },
-                            false => {
-                                let mut tmpamt = amt1;
-                                tmpamt.num *= -1;
-                                self.row_ops_mul(b, tmpamt);
-                                self.row_ops_add(a, b);
-                                self.row_ops_div(b, tmpamt);
-                                sign = String::from("+");
-                                neg = true;
-                            }
-                        }
-                        if print_steps {
-                            print!("R{} {} ({}) * R{} → R{0}\n{}\n\n", a + 1, sign, {
-                                if neg {
-                                    amt1.negative().try_simplify()
-                                } else {
-                                    amt1
-                                }
-                            }, b + 1, self);
-                        }
-                        continue;
-                    }
-                    if b == a { // On the diagonal line
-                        if amt1.num == 0 {
-                            let mut other: i32 = -1;
-                            // Find row beneath current one with a value in the columnn that the current
-                            // row's missing
-                            for i in (b..max).filter(|&i| i != a) {
-                                if self.clone().matrix[i][b].num != 0 {
-                                    other = i as i32;
-                                    break;
-                                }
-                            }
-                            if other == -1 { // It's okay if there isn't one - just move on
-                                continue;
-                            }
-                            let other = other as usize;
-                            let mut add = true;
-                            let amt2 = self.clone().matrix[other][b]; // Get second value
-                            match amt2.num > 0 {
-                                true => {
-                                    self.row_ops_add(b, other); // Get value in zero element
-                                }
-                                false => {
-                                    add = false;
-                                    self.row_ops_sub(b, other); // Get value in zero element
-                                }
-                            }
-                            let sign = match add {
-                                true => String::from("+"),
-                                false => String::from("-")
-                            };
-                            if print_steps {
-                                print!("R{} {} R{} → R{0}\n{}\n\n", a + 1, sign, other + 1, self);
-                            }
-                            let amt1 = self.clone().matrix[a][b]; // Refresh current value
-                            if amt1.num != 1 {
-                                self.row_ops_div(a, amt1);
-                                if print_steps {
-                                    let foo = amt1.clone().inverse();
-                                    print!("({}) * R{} → R{1}\n{}\n\n", foo, a + 1, self);
-                                }
-                            }
-                            continue;
-                        }
-                        self.row_ops_div(a, amt1); // Divide by self
-                        if print_steps {
-                            let amt1 = amt1.inverse();
-                            print!("({}) * R{} → R{1}\n{}\n\n", amt1, a + 1, self);
-                        }
-                        continue;
-                    }
+        // Copies `other` into this view entry by entry
+        pub fn assign(&mut self, other: &MatrixView<T>) {
+            assert!(self.dimension() == other.dimension(), "Views have different dimensions");
+            let (rows, cols) = self.dimension();
+            for a in 0..rows {
+                for b in 0..cols {
+                    self.set(a, b, other.get(a, b));
                 }
             }
         }
 
-        pub fn reduced_row_echelon_form(&mut self, print_steps: bool) {
-            self.row_echelon_form(print_steps);
-            if print_steps {
-                println!("------- Completed REF, starting RREF -------\n");
+        pub fn as_view(&self) -> MatrixView<'_, T> {
+            MatrixView {
+                data: self.data,
+                row_order: self.row_order,
+                window: self.window
             }
-            let max = cmp::min(self.dimension.0, self.dimension.1);
-            for a in (0..max - 1).rev() {
-                for b in (a + 1..max).rev() {
-                    let amt = self.clone().matrix[a][b];
-                    if !amt.cmp(&fracs::Frac::from(0)).eq(&fracs::CmpRes::Eq) {
-                        self.row_ops_mul(b, amt);
-                        self.row_ops_sub(a, b);
-                        self.row_ops_div(b, amt);
-                        if print_steps {
-                            print!("R{} - ({}) * R{} → R{0}\n{}\n\n", a + 1, amt, b + 1, self);
-                        }
-                    }
-                }
+        }
+
+        pub fn submatrix_mut(&mut self, row: usize, col: usize, dim: (usize, usize)) -> MatrixViewMut<'_, T> {
+            MatrixViewMut {
+                data: self.data,
+                row_order: self.row_order,
+                window: self.window.sub((row, dim.0), (col, dim.1))
             }
         }
 
-        // The inverse can be achieved by taking a matrix and transforming it into a unit matrix (RREF
-        // form) and applying the transformations to a unit matrix. The resulting non-unit matrix is the
-        // inverse of the original. This function combines the REF and RREF functions above and applies
-        // each transformation to a unit matrix.
-        pub fn inverse(&self, print_steps: bool) -> Result<Matrix, String> {
-            let mut slef = self.clone();
-            if slef.dimension.0 != slef.dimension.1 {
-                return Err(String::from("Matrix must be square in dimension to calculate the inverse."));
-            }
-            let mut unit = Matrix::from_dimension((slef.dimension.0, slef.dimension.1));
-            for a in 0..unit.dimension.0 {
-                unit.matrix[a][a] = fracs::Frac::from(1);
-            }
-            if print_steps {
-                print!("Setup at start of inverse calculation:\n{}\n\n", add_mat_to_string(slef.to_string(), &unit, Separator::Space));
-            }
-            let max = cmp::min(slef.dimension.0, slef.dimension.1);
-            for a in 0..max {
-                for b in 0..a + 1 { // Keep tested values "below" or on the diagonal line
-                    let amt1 = slef.clone().matrix[a][b]; // Current value
-                    if b < a { // "Under" the diagonal line
-                        if amt1.num == 0 {
-                            continue;
-                        }
-                        let mut sign = String::from("");
-                        let mut neg = false;
-                        match amt1.num > 0 {
-                            true => {
-                                slef.row_ops_mul(b, amt1);
-                                unit.row_ops_mul(b, amt1);
-                                slef.row_ops_sub(a, b);
-                                unit.row_ops_sub(a, b);
-                                slef.row_ops_div(b, amt1);
-                                unit.row_ops_div(b, amt1);
-                                sign = String::from("-");
-                            },
-                            false => {
-                                let mut tmpamt = amt1;
-                                tmpamt.num *= -1;
-                                slef.row_ops_mul(b, tmpamt);
-                                unit.row_ops_mul(b, tmpamt);
-                                slef.row_ops_add(a, b);
-                                unit.row_ops_add(a, b);
-                                slef.row_ops_div(b, tmpamt);
-                                unit.row_ops_div(b, tmpamt);
-                                sign = String::from("+");
-                                neg = true;
-                            }
-                        }
-                        if print_steps {
-                            print!("R{} {} ({}) * R{} → R{0}\n{}\n\n", a + 1, sign, {
-                                if neg {
-                                    amt1.negative().try_simplify()
-                                } else {
-                                    amt1
-                                }
-                            }, b + 1, add_mat_to_string(slef.to_string(), &unit, Separator::Space));
+        pub fn row_mut(&mut self, row: usize) -> MatrixViewMut<'_, T> {
+            let cols = self.dimension().1;
+            self.submatrix_mut(row, 0, (1, cols))
+        }
+
+        pub fn col_mut(&mut self, col: usize) -> MatrixViewMut<'_, T> {
+            let rows = self.dimension().0;
+            self.submatrix_mut(0, col, (rows, 1))
+        }
+
+        pub fn transpose_mut(self) -> MatrixViewMut<'a, T> {
+            MatrixViewMut {
+                window: self.window.transposed(),
+                ..self
+            }
+        }
+    }
+
+    impl<T: Field> Matrix<T> {
+        fn window(&self) -> Window {
+            Window {
+                stride: self.width,
+                row_start: 0,
+                col_start: 0,
+                height: self.height,
+                width: self.width,
+                transposed: false
+            }
+        }
+
+        pub fn view(&self) -> MatrixView<'_, T> {
+            MatrixView {
+                data: &self.data,
+                row_order: &self.row_order,
+                window: self.window()
+            }
+        }
+
+        pub fn view_mut(&mut self) -> MatrixViewMut<'_, T> {
+            let window = self.window();
+            MatrixViewMut {
+                data: &mut self.data,
+                row_order: &self.row_order,
+                window
+            }
+        }
+
+        pub fn submatrix(&self, row: usize, col: usize, dim: (usize, usize)) -> MatrixView<'_, T> {
+            self.view().submatrix(row, col, dim)
+        }
+
+        pub fn submatrix_mut(&mut self, row: usize, col: usize, dim: (usize, usize)) -> MatrixViewMut<'_, T> {
+            let window = self.window().sub((row, dim.0), (col, dim.1));
+            MatrixViewMut {
+                data: &mut self.data,
+                row_order: &self.row_order,
+                window
+            }
+        }
+
+        pub fn row_view(&self, row: usize) -> MatrixView<'_, T> {
+            self.view().row(row)
+        }
+
+        pub fn col_view(&self, col: usize) -> MatrixView<'_, T> {
+            self.view().col(col)
+        }
+
+        pub fn row_view_mut(&mut self, row: usize) -> MatrixViewMut<'_, T> {
+            let width = self.width;
+            self.submatrix_mut(row, 0, (1, width))
+        }
+
+        pub fn col_view_mut(&mut self, col: usize) -> MatrixViewMut<'_, T> {
+            let height = self.height;
+            self.submatrix_mut(0, col, (height, 1))
+        }
+
+        pub fn transpose_view(&self) -> MatrixView<'_, T> {
+            self.view().transpose()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use mats::Matrix;
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn views_read_without_copying() {
+            let a = matr("1, 2, 3; 4, 5, 6; 7, 8, 9");
+            assert_eq!(a.submatrix(1, 1, (2, 2)).to_matrix(), matr("5, 6; 8, 9"));
+            assert_eq!(a.row_view(1).to_matrix(), matr("4, 5, 6"));
+            assert_eq!(a.col_view(2).to_matrix(), matr("3; 6; 9"));
+            assert_eq!(a.transpose_view().to_matrix(), matr("1, 4, 7; 2, 5, 8; 3, 6, 9"));
+            // Views of views are still relative to the outer view, even through a transpose
+            let t = a.submatrix(0, 1, (3, 2)).transpose();
+            assert_eq!(t.dimension(), (2, 3));
+            assert_eq!(t.get(1, 0), a.view().get(0, 2));
+            assert_eq!(t.row(0).to_matrix(), matr("2, 5, 8"));
+        }
+
+        #[test]
+        fn swaps_only_move_the_row_order() {
+            let mut a = matr("1, 2; 3, 4; 5, 6");
+            let data = a.data.clone();
+            a.swap_rows(0, 2);
+            assert_eq!(a.data, data);
+            assert_eq!(a.row_view(0).to_matrix(), matr("5, 6"));
+            assert_eq!(a.col_view(1).to_matrix(), matr("6; 4; 2"));
+            let before = a.clone();
+            a.compact();
+            assert_eq!(a.row_order, vec![0, 1, 2]);
+            assert_eq!(a, before);
+        }
+
+        #[test]
+        fn mutable_views_write_through() {
+            let mut a = matr("1, 2, 3; 4, 5, 6");
+            a.swap_rows(0, 1);
+            a.col_view_mut(0).fill(From::from(0));
+            a.submatrix_mut(0, 1, (2, 2)).transpose_mut().set(0, 1, From::from(9));
+            assert_eq!(a, matr("0, 5, 6; 0, 9, 3"));
+            let b = matr("7, 8");
+            a.row_view_mut(1).submatrix_mut(0, 1, (1, 2)).assign(&b.view());
+            assert_eq!(a, matr("0, 5, 6; 0, 7, 8"));
+        }
+    }
+}
+
+// Element access, iteration and the usual functional helpers, so callers never need to know how
+// the entries are actually stored
+pub mod accessors {
+    use std::iter::FromIterator;
+    use std::ops::{Index, IndexMut};
+    use field::Field;
+    use mats::{Matrix, MatrixError};
+    use mats::views::MatrixView;
+
+    impl<T: Field> Index<(usize, usize)> for Matrix<T> {
+        type Output = T;
+
+        fn index(&self, pos: (usize, usize)) -> &T {
+            match self.get(pos.0, pos.1) {
+                Some(elem) => elem,
+                None => panic!("({}, {}) is outside of a {}x{} matrix", pos.0, pos.1, self.height, self.width)
+            }
+        }
+    }
+
+    impl<T: Field> IndexMut<(usize, usize)> for Matrix<T> {
+        fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+            let (height, width) = self.dimension();
+            match self.get_mut(pos.0, pos.1) {
+                Some(elem) => elem,
+                None => panic!("({}, {}) is outside of a {}x{} matrix", pos.0, pos.1, height, width)
+            }
+        }
+    }
+
+    // Panics if the rows aren't all the same length; use Matrix::from_rows to get an error instead.
+    // No rows at all gives a 0x0 matrix.
+    impl<T: Field> FromIterator<Vec<T>> for Matrix<T> {
+        fn from_iter<I: IntoIterator<Item = Vec<T>>>(iter: I) -> Self {
+            let rows: Vec<Vec<T>> = iter.into_iter().collect();
+            if rows.is_empty() {
+                return Matrix::from_dimension((0, 0));
+            }
+            match Matrix::from_rows(rows) {
+                Ok(matr) => matr,
+                Err(e) => panic!("{}", e)
+            }
+        }
+    }
+
+    impl<T: Field> Matrix<T> {
+        pub fn from_fn<F: FnMut(usize, usize) -> T>(rows: usize, cols: usize, mut f: F) -> Self {
+            let mut data = Vec::with_capacity(rows * cols);
+            for a in 0..rows {
+                for b in 0..cols {
+                    data.push(f(a, b));
+                }
+            }
+            Matrix {
+                height: rows,
+                width: cols,
+                data,
+                row_order: (0..rows).collect()
+            }
+        }
+
+        pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+            if row >= self.height || col >= self.width {
+                return None;
+            }
+            Some(&self.data[self.pos(row, col)])
+        }
+
+        pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+            if row >= self.height || col >= self.width {
+                return None;
+            }
+            Some(self.at_mut(row, col))
+        }
+
+        pub fn rows(&self) -> impl Iterator<Item = MatrixView<'_, T>> {
+            (0..self.height).map(move |a| self.row_view(a))
+        }
+
+        pub fn cols(&self) -> impl Iterator<Item = MatrixView<'_, T>> {
+            (0..self.width).map(move |b| self.col_view(b))
+        }
+
+        // Entries in row-major order along with their (row, column)
+        pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+            (0..self.height).flat_map(move |a| self.row_slice(a).iter().enumerate().map(move |(b, elem)| ((a, b), elem)))
+        }
+
+        pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+            // Hand out each physical row exactly once, in logical order
+            let width = self.width;
+            let mut physical: Vec<Option<&mut [T]>> = self.data.chunks_mut(width.max(1)).map(Some).collect();
+            self.row_order.iter().enumerate().flat_map(move |(a, &p)| {
+                let row: &mut [T] = physical.get_mut(p).and_then(|row| row.take()).unwrap_or_default();
+                row.iter_mut().enumerate().map(move |(b, elem)| ((a, b), elem))
+            })
+        }
+
+        pub fn map<U: Field, F: FnMut(T) -> U>(&self, mut f: F) -> Matrix<U> {
+            Matrix::from_fn(self.height, self.width, |a, b| f(self.at(a, b)))
+        }
+
+        // Entry-wise combination of two matrices of the same dimension
+        pub fn zip_with<U: Field, V: Field, F: FnMut(T, U) -> V>(&self, other: &Matrix<U>, mut f: F) -> Result<Matrix<V>, MatrixError> {
+            if self.dimension() != other.dimension() {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: other.dimension(), op: "zip_with" });
+            }
+            Ok(Matrix::from_fn(self.height, self.width, |a, b| f(self.at(a, b), other.at(a, b))))
+        }
+
+        // Folds over the entries in row-major order
+        pub fn fold<A, F: FnMut(A, T) -> A>(&self, init: A, mut f: F) -> A {
+            self.iter().fold(init, |acc, (_, &elem)| f(acc, elem))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use fracs::Frac;
+        use mats::Matrix;
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn indexing_and_bounds_checked_access() {
+            let mut a = matr("1, 2, 3; 4, 5, 6");
+            a.swap_rows(0, 1);
+            assert_eq!(a[(0, 2)], Frac::from(6));
+            a[(1, 0)] = Frac::new(1, 2);
+            assert_eq!(a.get(1, 0), Some(&Frac::new(1, 2)));
+            assert_eq!(a.get(2, 0), None);
+            assert_eq!(a.get_mut(0, 3), None);
+            *a.get_mut(0, 0).unwrap() = Frac::from(0);
+            assert_eq!(a, matr("0, 5, 6; 1/2, 2, 3"));
+        }
+
+        #[test]
+        #[should_panic]
+        fn indexing_out_of_bounds_panics() {
+            let a = matr("1, 2; 3, 4");
+            let _ = a[(0, 2)];
+        }
+
+        #[test]
+        fn iterators_follow_the_logical_order() {
+            let mut a = matr("1, 2; 3, 4; 5, 6");
+            a.swap_rows(0, 2);
+            let entries: Vec<((usize, usize), Frac)> = a.iter().map(|(pos, &elem)| (pos, elem)).collect();
+            assert_eq!(entries[0], ((0, 0), Frac::from(5)));
+            assert_eq!(entries[5], ((2, 1), Frac::from(2)));
+            assert_eq!(a.rows().map(|row| row.to_matrix()).nth(1), Some(matr("3, 4")));
+            assert_eq!(a.cols().count(), 2);
+            for ((row, col), elem) in a.iter_mut() {
+                *elem = Frac::from((10 * row + col) as i32);
+            }
+            assert_eq!(a, matr("0, 1; 10, 11; 20, 21"));
+        }
+
+        #[test]
+        fn combinators() {
+            let a = Matrix::from_fn(2, 3, |a, b| Frac::from((a * 3 + b + 1) as i32));
+            assert_eq!(a, matr("1, 2, 3; 4, 5, 6"));
+            assert_eq!(a.map(|elem| elem.mul(elem)), matr("1, 4, 9; 16, 25, 36"));
+            assert_eq!(a.fold(Frac::from(0), |acc, elem| acc.add(elem)), Frac::from(21));
+            let b = matr("6, 5, 4; 3, 2, 1");
+            assert_eq!(a.zip_with(&b, |x, y| x.add(y)), Ok(Matrix::from_fn(2, 3, |_, _| Frac::from(7))));
+            assert!(a.zip_with(&b.transpose(), |x, y| x.add(y)).is_err());
+            let c: Matrix = vec![vec![Frac::from(1)], vec![Frac::from(2)]].into_iter().collect();
+            assert_eq!(c, matr("1; 2"));
+            let empty: Matrix = Vec::new().into_iter().collect();
+            assert_eq!(empty.dimension(), (0, 0));
+        }
+    }
+}
+
+// The usual suspects for testing elimination and inverses. Everything is built from the Field
+// operations, so fraction matrices come out exact.
+pub mod special {
+    use field::Field;
+    use mats::{Matrix, MatrixError};
+
+    impl<T: Field> Matrix<T> {
+        // H[i][j] = 1 / (i + j + 1), famously badly conditioned but exactly invertible
+        pub fn hilbert(n: usize) -> Matrix<T> {
+            Matrix::from_fn(n, n, |i, j| T::from_i32((i + j + 1) as i32).inverse())
+        }
+
+        // V[i][j] = x_i^j. Invertible exactly when the points are distinct.
+        pub fn vandermonde(xs: &[T]) -> Matrix<T> {
+            let mut ret = Matrix::from_dimension((xs.len(), xs.len()));
+            for (i, &x) in xs.iter().enumerate() {
+                let mut power = T::one();
+                for j in 0..xs.len() {
+                    *ret.at_mut(i, j) = power;
+                    power = power.mul(x);
+                }
+            }
+            ret
+        }
+
+        // Constant along each diagonal, given the first column and first row. The corner comes
+        // from the column, so row[0] is ignored.
+        pub fn toeplitz(col: &[T], row: &[T]) -> Matrix<T> {
+            Matrix::from_fn(col.len(), row.len(), |i, j| if i >= j { col[i - j] } else { row[j - i] })
+        }
+
+        // Constant along each anti-diagonal, given the first column and last row. The corner comes
+        // from the column, so row[0] is ignored.
+        pub fn hankel(col: &[T], row: &[T]) -> Matrix<T> {
+            let height = col.len();
+            Matrix::from_fn(height, row.len(), |i, j| if i + j < height { col[i + j] } else { row[i + j + 1 - height] })
+        }
+
+        // Every row is the one above it shifted one place to the right, wrapping around
+        pub fn circulant(first_row: &[T]) -> Matrix<T> {
+            let n = first_row.len();
+            Matrix::from_fn(n, n, |i, j| first_row[(j + n - i) % n])
+        }
+
+        // Frobenius companion matrix of a polynomial, coefficients highest degree first. Ones sit
+        // under the diagonal and the last column holds the negated coefficients of the monic
+        // polynomial, so the characteristic polynomial is the one given (divided through by its
+        // leading coefficient).
+        pub fn companion(coeffs: &[T]) -> Result<Matrix<T>, MatrixError> {
+            let coeffs = match coeffs.iter().position(|elem| !elem.is_zero()) {
+                Some(start) => &coeffs[start..],
+                None => return Err(MatrixError::Empty)
+            };
+            let n = coeffs.len() - 1;
+            if n == 0 {
+                return Err(MatrixError::Empty);
+            }
+            let lead = coeffs[0];
+            let mut ret = Matrix::from_dimension((n, n));
+            for a in 1..n {
+                *ret.at_mut(a, a - 1) = T::one();
+            }
+            // coeffs[n] is the constant term, which goes in the top row
+            for a in 0..n {
+                *ret.at_mut(a, n - 1) = coeffs[n - a].div(lead).negative();
+            }
+            Ok(ret)
+        }
+
+        // Symmetric Pascal matrix, P[i][j] = (i + j choose i)
+        pub fn pascal(n: usize) -> Matrix<T> {
+            let mut ret: Matrix<T> = Matrix::from_dimension((n, n));
+            for i in 0..n {
+                for j in 0..n {
+                    *ret.at_mut(i, j) = if i == 0 || j == 0 {
+                        T::one()
+                    } else {
+                        ret.at(i - 1, j).add(ret.at(i, j - 1))
+                    };
+                }
+            }
+            ret
+        }
+
+        // C[i][j] = 1 / (x_i - y_j). None if some x_i equals some y_j.
+        pub fn cauchy(xs: &[T], ys: &[T]) -> Option<Matrix<T>> {
+            if xs.iter().any(|x| ys.contains(x)) {
+                return None;
+            }
+            Some(Matrix::from_fn(xs.len(), ys.len(), |i, j| xs[i].sub(ys[j]).inverse()))
+        }
+
+        // L[i][j] = min(i, j) / max(i, j), counting from 1
+        pub fn lehmer(n: usize) -> Matrix<T> {
+            Matrix::from_fn(n, n, |i, j| {
+                T::from_i32((i.min(j) + 1) as i32).div(T::from_i32((i.max(j) + 1) as i32))
+            })
+        }
+
+        // λ on the diagonal and ones just above it
+        pub fn jordan_block(lambda: T, n: usize) -> Matrix<T> {
+            let mut ret = Matrix::scalar(n, lambda);
+            for a in 1..n {
+                *ret.at_mut(a - 1, a) = T::one();
+            }
+            ret
+        }
+
+        // Rotation by k quarter turns counterclockwise. Negative k turns clockwise.
+        pub fn quarter_turn(k: i32) -> Matrix<T> {
+            let (one, zero) = (T::one(), T::zero());
+            let rows = match k.rem_euclid(4) {
+                0 => [[one, zero], [zero, one]],
+                1 => [[zero, one.negative()], [one, zero]],
+                2 => [[one.negative(), zero], [zero, one.negative()]],
+                _ => [[zero, one], [one.negative(), zero]]
+            };
+            Matrix::from_fn(2, 2, |i, j| rows[i][j])
+        }
+
+        // Row i has its one in column perm[i], so P * A puts row perm[i] of A in row i. None if
+        // perm isn't a permutation of 0..n.
+        pub fn permutation(perm: &[usize]) -> Option<Matrix<T>> {
+            let n = perm.len();
+            let mut seen = vec![false; n];
+            for &p in perm {
+                if p >= n || seen[p] {
+                    return None;
+                }
+                seen[p] = true;
+            }
+            Some(Matrix::from_fn(n, n, |i, j| if perm[i] == j { T::one() } else { T::zero() }))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use fracs::Frac;
+        use mats::{Matrix, MatrixError};
+        use mats::format::Silent;
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        fn fracs(nums: &[i32]) -> Vec<Frac> {
+            nums.iter().map(|&n| Frac::from(n)).collect()
+        }
+
+        #[test]
+        fn reciprocal_matrices() {
+            let h: Matrix = Matrix::hilbert(3);
+            assert_eq!(h, matr("1, 1/2, 1/3; 1/2, 1/3, 1/4; 1/3, 1/4, 1/5"));
+            assert_eq!(h.inverse(&mut Silent), Ok(matr("9, -36, 30; -36, 192, -180; 30, -180, 180")));
+            assert_eq!(Matrix::cauchy(&fracs(&[1, 2]), &fracs(&[3, 4])), Some(matr("-1/2, -1/3; -1, -1/2")));
+            assert_eq!(Matrix::cauchy(&fracs(&[1, 2]), &fracs(&[2])), None);
+            assert_eq!(Matrix::lehmer(3), matr("1, 1/2, 1/3; 1/2, 1, 2/3; 1/3, 2/3, 1"));
+        }
+
+        #[test]
+        fn structured_matrices() {
+            let v = Matrix::vandermonde(&fracs(&[1, 2, 3]));
+            assert_eq!(v, matr("1, 1, 1; 1, 2, 4; 1, 3, 9"));
+            assert_eq!(v.determinant(), Ok(Frac::from(2)));
+            assert_eq!(Matrix::toeplitz(&fracs(&[1, 2, 3]), &fracs(&[9, 4, 5])), matr("1, 4, 5; 2, 1, 4; 3, 2, 1"));
+            assert_eq!(Matrix::hankel(&fracs(&[1, 2, 3]), &fracs(&[9, 4, 5])), matr("1, 2, 3; 2, 3, 4; 3, 4, 5"));
+            assert_eq!(Matrix::toeplitz(&fracs(&[1, 2]), &fracs(&[9, 3, 4])), matr("1, 3, 4; 2, 1, 3"));
+            assert_eq!(Matrix::circulant(&fracs(&[1, 2, 3])), matr("1, 2, 3; 3, 1, 2; 2, 3, 1"));
+            let p: Matrix = Matrix::pascal(4);
+            assert_eq!(p, matr("1, 1, 1, 1; 1, 2, 3, 4; 1, 3, 6, 10; 1, 4, 10, 20"));
+            assert_eq!(p.determinant(), Ok(Frac::from(1)));
+            assert_eq!(Matrix::jordan_block(Frac::from(2), 3), matr("2, 1, 0; 0, 2, 1; 0, 0, 2"));
+        }
+
+        #[test]
+        fn companion_matrices() {
+            // 2x^2 - 6x + 4 = 2(x - 1)(x - 2)
+            let c = Matrix::companion(&fracs(&[0, 2, -6, 4])).unwrap();
+            assert_eq!(c, matr("0, -2; 1, 3"));
+            assert_eq!((c.trace(), c.determinant()), (Ok(Frac::from(3)), Ok(Frac::from(2))));
+            assert_eq!(Matrix::companion(&fracs(&[0, 0])), Err(MatrixError::Empty));
+            assert_eq!(Matrix::companion(&fracs(&[5])), Err(MatrixError::Empty));
+        }
+
+        #[test]
+        fn rotations_and_permutations() {
+            let turn: Matrix = Matrix::quarter_turn(1);
+            assert_eq!(turn, matr("0, -1; 1, 0"));
+            assert_eq!(&turn * &turn, Matrix::quarter_turn(2));
+            assert_eq!(Matrix::<Frac>::quarter_turn(-1), Matrix::quarter_turn(3));
+            assert_eq!(Matrix::<Frac>::quarter_turn(4), Matrix::identity(2));
+
+            let p: Matrix = Matrix::permutation(&[2, 0, 1]).unwrap();
+            assert_eq!(p, matr("0, 0, 1; 1, 0, 0; 0, 1, 0"));
+            assert_eq!(&p * &matr("1; 2; 3"), matr("3; 1; 2"));
+            assert_eq!(Matrix::<Frac>::permutation(&[0, 0]), None);
+            assert_eq!(Matrix::<Frac>::permutation(&[0, 2]), None);
+        }
+    }
+}
+
+// Whole-matrix structure: transposing, and pulling out, replacing, inserting and removing rows and
+// columns. Rows and columns handed in or out are plain slices and Vecs in order.
+pub mod structure {
+    use field::Field;
+    use mats::{Matrix, MatrixError};
+
+    impl<T: Field> Matrix<T> {
+        pub fn transpose(&self) -> Matrix<T> {
+            self.transpose_view().to_matrix()
+        }
+
+        pub fn trace(&self) -> Result<T, MatrixError> {
+            if self.height != self.width {
+                return Err(MatrixError::NotSquare { dimension: self.dimension() });
+            }
+            Ok((0..self.height).fold(T::zero(), |acc, a| acc.add(self.at(a, a))))
+        }
+
+        // 1 x width copy of row i
+        pub fn row(&self, i: usize) -> Matrix<T> {
+            self.row_view(i).to_matrix()
+        }
+
+        // height x 1 copy of column j
+        pub fn col(&self, j: usize) -> Matrix<T> {
+            self.col_view(j).to_matrix()
+        }
+
+        pub fn set_row(&mut self, i: usize, row: &[T]) -> Result<(), MatrixError> {
+            check_index("row", i, self.height)?;
+            self.check_row(row, "set_row")?;
+            self.row_slice_mut(i).copy_from_slice(row);
+            Ok(())
+        }
+
+        pub fn set_col(&mut self, j: usize, col: &[T]) -> Result<(), MatrixError> {
+            check_index("column", j, self.width)?;
+            self.check_col(col, "set_col")?;
+            for (a, &elem) in col.iter().enumerate() {
+                *self.at_mut(a, j) = elem;
+            }
+            Ok(())
+        }
+
+        // Inserts before row i, so i == height appends. An empty 0x0 matrix takes its width from the
+        // new row.
+        pub fn insert_row(&mut self, i: usize, row: &[T]) -> Result<(), MatrixError> {
+            if i > self.height {
+                return Err(MatrixError::OutOfBounds { axis: "row", index: i, len: self.height });
+            }
+            if self.height == 0 && self.width == 0 {
+                self.width = row.len();
+            }
+            self.check_row(row, "insert_row")?;
+            // New rows go at the end of the buffer - only the order needs to know where they belong
+            self.data.extend_from_slice(row);
+            self.row_order.insert(i, self.height);
+            self.height += 1;
+            Ok(())
+        }
+
+        pub fn remove_row(&mut self, i: usize) -> Result<Vec<T>, MatrixError> {
+            check_index("row", i, self.height)?;
+            self.compact();
+            self.row_order.pop();
+            self.height -= 1;
+            Ok(self.data.drain(i * self.width..(i + 1) * self.width).collect())
+        }
+
+        // Inserts before column j, so j == width appends. An empty 0x0 matrix takes its height from
+        // the new column.
+        pub fn insert_col(&mut self, j: usize, col: &[T]) -> Result<(), MatrixError> {
+            if j > self.width {
+                return Err(MatrixError::OutOfBounds { axis: "column", index: j, len: self.width });
+            }
+            if self.height == 0 && self.width == 0 {
+                self.height = col.len();
+                self.row_order = (0..col.len()).collect();
+            }
+            self.check_col(col, "insert_col")?;
+            let mut data = Vec::with_capacity(self.data.len() + self.height);
+            for (a, &elem) in col.iter().enumerate() {
+                let row = self.row_slice(a);
+                data.extend_from_slice(&row[..j]);
+                data.push(elem);
+                data.extend_from_slice(&row[j..]);
+            }
+            self.data = data;
+            self.row_order = (0..self.height).collect();
+            self.width += 1;
+            Ok(())
+        }
+
+        pub fn remove_col(&mut self, j: usize) -> Result<Vec<T>, MatrixError> {
+            check_index("column", j, self.width)?;
+            let removed = (0..self.height).map(|a| self.at(a, j)).collect();
+            self.data = (0..self.height).flat_map(|a| {
+                let row = self.row_slice(a);
+                row[..j].iter().chain(row[j + 1..].iter()).cloned().collect::<Vec<T>>()
+            }).collect();
+            self.row_order = (0..self.height).collect();
+            self.width -= 1;
+            Ok(removed)
+        }
+
+        fn check_row(&self, row: &[T], op: &'static str) -> Result<(), MatrixError> {
+            if row.len() != self.width {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: (1, row.len()), op });
+            }
+            Ok(())
+        }
+
+        fn check_col(&self, col: &[T], op: &'static str) -> Result<(), MatrixError> {
+            if col.len() != self.height {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: (col.len(), 1), op });
+            }
+            Ok(())
+        }
+    }
+
+    fn check_index(axis: &'static str, index: usize, len: usize) -> Result<(), MatrixError> {
+        if index >= len {
+            return Err(MatrixError::OutOfBounds { axis, index, len });
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use fracs::Frac;
+        use mats::{Matrix, MatrixError};
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        fn fracs(nums: &[i32]) -> Vec<Frac> {
+            nums.iter().map(|&n| Frac::from(n)).collect()
+        }
+
+        #[test]
+        fn constructors() {
+            assert_eq!(Matrix::identity(2), matr("1, 0; 0, 1"));
+            assert_eq!(Matrix::zeros(2, 3), matr("0, 0, 0; 0, 0, 0"));
+            assert_eq!(Matrix::ones(1, 2), matr("1, 1"));
+            assert_eq!(Matrix::diag(&fracs(&[2, -1])), matr("2, 0; 0, -1"));
+            assert_eq!(Matrix::scalar(2, Frac::new(1, 2)), matr("1/2, 0; 0, 1/2"));
+            assert_eq!(Matrix::from_flat(2, fracs(&[1, 2, 3, 4])), Ok(matr("1, 2; 3, 4")));
+            assert_eq!(Matrix::<Frac>::zeros(3, 0).dimension(), (3, 0));
+        }
+
+        #[test]
+        fn transpose_trace_rows_and_columns() {
+            let mut a = matr("1, 2, 3; 4, 5, 6");
+            a.swap_rows(0, 1);
+            assert_eq!(a.transpose(), matr("4, 1; 5, 2; 6, 3"));
+            assert_eq!(a.trace(), Err(MatrixError::NotSquare { dimension: (2, 3) }));
+            assert_eq!(matr("1, 2; 3, 4").trace(), Ok(Frac::from(5)));
+            assert_eq!(a.row(1), matr("1, 2, 3"));
+            assert_eq!(a.col(2), matr("6; 3"));
+        }
+
+        #[test]
+        fn setting_inserting_and_removing() {
+            let mut a = matr("1, 2; 3, 4");
+            a.set_row(0, &fracs(&[5, 6])).unwrap();
+            a.set_col(1, &fracs(&[7, 8])).unwrap();
+            assert_eq!(a, matr("5, 7; 3, 8"));
+            a.insert_row(1, &fracs(&[0, 0])).unwrap();
+            a.insert_col(2, &fracs(&[1, 2, 3])).unwrap();
+            assert_eq!(a, matr("5, 7, 1; 0, 0, 2; 3, 8, 3"));
+            assert_eq!(a.remove_row(0), Ok(fracs(&[5, 7, 1])));
+            assert_eq!(a.remove_col(1), Ok(fracs(&[0, 8])));
+            assert_eq!(a, matr("0, 2; 3, 3"));
+            assert_eq!(a.dimension(), (2, 2));
+        }
+
+        #[test]
+        fn bad_indices_and_lengths_are_errors() {
+            let mut a = matr("1, 2; 3, 4");
+            let oob = |axis, index| Err(MatrixError::OutOfBounds { axis, index, len: 2 });
+            assert_eq!(a.set_row(2, &fracs(&[0, 0])), oob("row", 2));
+            assert_eq!(a.set_col(5, &fracs(&[0, 0])), oob("column", 5));
+            assert_eq!(a.insert_row(3, &fracs(&[0, 0])), oob("row", 3));
+            assert_eq!(a.insert_col(3, &fracs(&[0, 0])), oob("column", 3));
+            assert_eq!(a.remove_row(2), Err(MatrixError::OutOfBounds { axis: "row", index: 2, len: 2 }));
+            assert_eq!(a.remove_col(2), Err(MatrixError::OutOfBounds { axis: "column", index: 2, len: 2 }));
+            assert_eq!(a.set_row(0, &fracs(&[0])), Err(MatrixError::DimensionMismatch { left: (2, 2), right: (1, 1), op: "set_row" }));
+            assert_eq!(a.insert_col(0, &fracs(&[0])), Err(MatrixError::DimensionMismatch { left: (2, 2), right: (1, 1), op: "insert_col" }));
+            assert_eq!(a, matr("1, 2; 3, 4"));
+            assert_eq!(MatrixError::OutOfBounds { axis: "row", index: 3, len: 2 }.to_string(),
+                       "There's no row 4 - the matrix only has 2 rows.");
+        }
+
+        #[test]
+        fn growing_empty_matrices() {
+            // n x 0 keeps its n rows, and only takes a column of that length
+            let mut tall: Matrix = Matrix::zeros(3, 0);
+            assert_eq!(tall.insert_col(0, &fracs(&[1, 2])), Err(MatrixError::DimensionMismatch { left: (3, 0), right: (2, 1), op: "insert_col" }));
+            tall.insert_col(0, &fracs(&[1, 2, 3])).unwrap();
+            assert_eq!(tall, matr("1; 2; 3"));
+
+            let mut empty: Matrix = Matrix::zeros(0, 0);
+            empty.insert_row(0, &fracs(&[1, 2])).unwrap();
+            empty.insert_row(1, &fracs(&[3, 4])).unwrap();
+            assert_eq!(empty, matr("1, 2; 3, 4"));
+
+            let mut empty: Matrix = Matrix::zeros(0, 0);
+            empty.insert_col(0, &fracs(&[1, 2])).unwrap();
+            assert_eq!(empty, matr("1; 2"));
+        }
+    }
+}
+
+pub mod format {
+    use std::fmt;
+    use std::io;
+    use fracs;
+    use field::Field;
+    use mats::Matrix;
+    use mats::steps::Step;
+
+    // Where step-by-step output goes. Everything that used to print straight to stdout takes one
+    // of these instead, so the same calculation can print, fill a buffer, write to a file or stay
+    // silent.
+    pub trait StepSink<T: Field = fracs::Frac> {
+        fn emit(&mut self, text: &str);
+
+        // Lets callers skip building strings nobody will read
+        fn is_enabled(&self) -> bool {
+            true
+        }
+
+        // Row operations come through here rather than `emit`, so sinks that want the structured
+        // step can keep it. Text sinks just get it formatted the way it's always been printed.
+        fn record(&mut self, step: &Step<T>) {
+            self.emit(&format!("{}\n\n", step));
+        }
+
+        // Lets callers skip snapshotting the matrix after every operation
+        fn wants_steps(&self) -> bool {
+            self.is_enabled()
+        }
+    }
+
+    pub struct PrintSink;
+
+    impl<T: Field> StepSink<T> for PrintSink {
+        fn emit(&mut self, text: &str) {
+            print!("{}", text);
+        }
+    }
+
+    pub struct Silent;
+
+    impl<T: Field> StepSink<T> for Silent {
+        fn emit(&mut self, _text: &str) {}
+
+        fn is_enabled(&self) -> bool {
+            false
+        }
+    }
+
+    impl<T: Field> StepSink<T> for String {
+        fn emit(&mut self, text: &str) {
+            self.push_str(text);
+        }
+    }
+
+    // Any io::Write - a file, a socket, a Vec<u8>. Steps can't fail, so the first write error is
+    // kept here and everything after it is dropped.
+    pub struct WriteSink<W: io::Write> {
+        pub writer: W,
+        pub error: Option<io::Error>
+    }
+
+    impl<W: io::Write> WriteSink<W> {
+        pub fn new(writer: W) -> Self {
+            WriteSink {
+                writer,
+                error: None
+            }
+        }
+    }
+
+    impl<T: Field, W: io::Write> StepSink<T> for WriteSink<W> {
+        fn emit(&mut self, text: &str) {
+            if self.error.is_none() {
+                if let Err(e) = self.writer.write_all(text.as_bytes()) {
+                    self.error = Some(e);
+                }
+            }
+        }
+
+        fn is_enabled(&self) -> bool {
+            self.error.is_none()
+        }
+    }
+
+    pub enum Separator {
+        Plus,
+        Minus,
+        Times,
+        Divide,
+        Equals,
+        Space
+    }
+
+    impl fmt::Display for Separator {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                Separator::Plus => write!(f, "+"),
+                Separator::Minus => write!(f, "-"),
+                Separator::Times => write!(f, "*"),
+                Separator::Divide => write!(f, "/"),
+                Separator::Equals => write!(f, "="),
+                Separator::Space => write!(f, " ")
+            }
+        }
+    }
+
+    // Puts the matrix to the right of whatever's already in the string, vertically centering the
+    // shorter of the two against the taller one. The separator goes on the middle line.
+    pub fn add_mat_to_string<T: Field>(string: String, matr: &Matrix<T>, separator: Separator) -> String {
+        add_block_to_string(string, &matr.to_string(), separator)
+    }
+
+    // Same as above for any block of text, e.g. a coefficient between two matrices
+    pub fn add_block_to_string(string: String, block: &str, separator: Separator) -> String {
+        let lines_vec = string.lines().map(String::from).collect::<Vec<String>>();
+        let mat_vec = block.lines().map(String::from).collect::<Vec<String>>();
+        let height = lines_vec.len().max(mat_vec.len());
+        // Extra space above the shorter block gets the odd line, if there is one
+        let lines_top = (height - lines_vec.len()).div_ceil(2);
+        let mat_top = (height - mat_vec.len()).div_ceil(2);
+        let width = lines_vec.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut new_lines: Vec<String> = Vec::with_capacity(height);
+        for i in 0..height {
+            let left = match i.checked_sub(lines_top).and_then(|j| lines_vec.get(j)) {
+                Some(line) => line.clone(),
+                None => String::from("")
+            };
+            let right = match i.checked_sub(mat_top).and_then(|j| mat_vec.get(j)) {
+                Some(line) => line.as_str(),
+                None => ""
+            };
+            let padding = " ".repeat(width - left.chars().count());
+            let line = if i == height / 2 {
+                format!("{}{} {} {}", left, padding, separator, right)
+            } else {
+                format!("{}{}   {}", left, padding, right)
+            };
+            new_lines.push(String::from(line.trim_end()));
+        }
+        new_lines.join("\n")
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::io;
+        use fracs::Frac;
+        use mats::{Matrix, TSOpts};
+        use mats::format::*;
+
+        struct Broken;
+
+        impl io::Write for Broken {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn string_and_write_sinks_capture_the_steps() {
+            let mut a = matr("2, 4; 3, 5");
+            let mut text = String::new();
+            a.try_simplify_matrix(&mut text);
+            assert!(text.starts_with("Attempting to simplify matrix.\n\n"));
+            assert!(text.contains("Was able to simplify."));
+            assert_eq!(a, matr("1, 2; 3, 5"));
+
+            let mut sink = WriteSink::new(Vec::new());
+            matr("2, 4; 3, 5").try_simplify_matrix(&mut sink);
+            assert_eq!(String::from_utf8(sink.writer).unwrap(), text);
+        }
+
+        #[test]
+        fn quiet_sinks_and_constructors() {
+            let mut a = matr("2, 4; 3, 5");
+            a.try_simplify_matrix(&mut Silent);
+            assert_eq!(a, matr("1, 2; 3, 5"));
+            assert!(!StepSink::<Frac>::is_enabled(&Silent));
+
+            let mut broken = WriteSink::new(Broken);
+            matr("2, 4").try_simplify_matrix(&mut broken);
+            assert!(broken.error.is_some());
+            assert!(!StepSink::<Frac>::is_enabled(&broken));
+
+            // Simplifying without printing is the only way constructors touch a sink
+            let b = Matrix::from_i32_vec(2, vec![2, 4, 3, 5], TSOpts::from((true, false))).unwrap();
+            assert_eq!(b, matr("1, 2; 3, 5"));
+            assert_eq!(Matrix::from_i32_vec(2, vec![2, 4], TSOpts::default()).unwrap(), matr("2, 4"));
+        }
+
+        #[test]
+        fn blocks_line_up_side_by_side() {
+            let joined = add_mat_to_string(matr("1; 2; 3").to_string(), &matr("4"), Separator::Equals);
+            assert_eq!(joined, "⎡ 1 ⎤\n⎢ 2 ⎥ = ⎡ 4 ⎤\n⎣ 3 ⎦");
+        }
+    }
+}
+
+pub mod operations {
+    use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+    use field::Field;
+    use mats::{Matrix, MatrixError};
+    use mats::format::*;
+    use mats::systems::MatrixSolution;
+
+    impl<T: Field> Matrix<T> {
+        // "Divide" by solving X * other = self, which is self * other⁻¹ when other is invertible but
+        // works for any shape as long as the answer is unique. The elimination steps go to the same
+        // sink.
+        pub fn try_div(&self, other: &Matrix<T>, sink: &mut dyn StepSink<T>) -> Result<Matrix<T>, MatrixError> {
+            if self.width != other.width {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: other.dimension(), op: "division" });
+            }
+            if sink.is_enabled() {
+                sink.emit(&format!("{}\n\n", add_mat_to_string(self.to_string(), other, Separator::Divide)));
+            }
+            match other.solve_right(self, sink)? {
+                MatrixSolution::Unique(x) => Ok(x),
+                MatrixSolution::Infinite { free_vars, .. } => Err(MatrixError::NotUnique { free_vars }),
+                MatrixSolution::Inconsistent { rhs, .. } => Err(MatrixError::NoSolution { rhs })
+            }
+        }
+
+        pub fn try_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+            if self.dimension() != other.dimension() {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: other.dimension(), op: "addition" });
+            }
+            Ok(Matrix::from_fn(self.height, self.width, |a, b| self.at(a, b).add(other.at(a, b))))
+        }
+
+        pub fn try_sub(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+            if self.dimension() != other.dimension() {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: other.dimension(), op: "subtraction" });
+            }
+            Ok(Matrix::from_fn(self.height, self.width, |a, b| self.at(a, b).sub(other.at(a, b))))
+        }
+
+        pub fn try_mul(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+            if self.width != other.height {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: other.dimension(), op: "multiplication" });
+            }
+            // i-k-j order, so the inner loop runs along rows of `other` and `ret` instead of down
+            // columns
+            let mut ret: Matrix<T> = Matrix::from_dimension((self.height, other.width));
+            for a in 0..self.height {
+                for (b, &elem) in self.row_slice(a).iter().enumerate() {
+                    if elem.is_zero() {
+                        continue;
+                    }
+                    for (total, &o) in ret.row_slice_mut(a).iter_mut().zip(other.row_slice(b)) {
+                        *total = total.add(elem.mul(o));
+                    }
+                }
+            }
+            Ok(ret)
+        }
+
+        pub fn scale(&self, k: T) -> Matrix<T> {
+            self.map(|elem| elem.mul(k))
+        }
+    }
+
+    // Operators panic on mismatched dimensions, like slice indexing does. Use the try_ methods to
+    // get an error back instead.
+    fn unwrap_op<T: Field>(res: Result<Matrix<T>, MatrixError>) -> Matrix<T> {
+        match res {
+            Ok(matr) => matr,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    // Implements a binary operator for every combination of owned and borrowed operands in terms of
+    // the borrowed-borrowed version
+    macro_rules! matrix_binop {
+        ($op:ident, $method:ident, $try_method:ident) => {
+            impl<'a, 'b, T: Field> $op<&'b Matrix<T>> for &'a Matrix<T> {
+                type Output = Matrix<T>;
+
+                fn $method(self, other: &'b Matrix<T>) -> Matrix<T> {
+                    unwrap_op(self.$try_method(other))
+                }
+            }
+
+            impl<'b, T: Field> $op<&'b Matrix<T>> for Matrix<T> {
+                type Output = Matrix<T>;
+
+                fn $method(self, other: &'b Matrix<T>) -> Matrix<T> {
+                    unwrap_op(self.$try_method(other))
+                }
+            }
+
+            impl<'a, T: Field> $op<Matrix<T>> for &'a Matrix<T> {
+                type Output = Matrix<T>;
+
+                fn $method(self, other: Matrix<T>) -> Matrix<T> {
+                    unwrap_op(self.$try_method(&other))
+                }
+            }
+
+            impl<T: Field> $op<Matrix<T>> for Matrix<T> {
+                type Output = Matrix<T>;
+
+                fn $method(self, other: Matrix<T>) -> Matrix<T> {
+                    unwrap_op(self.$try_method(&other))
+                }
+            }
+        }
+    }
+
+    matrix_binop!(Add, add, try_add);
+    matrix_binop!(Sub, sub, try_sub);
+    matrix_binop!(Mul, mul, try_mul);
+
+    impl<T: Field> Neg for &Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn neg(self) -> Matrix<T> {
+            self.map(|elem| elem.negative())
+        }
+    }
+
+    impl<T: Field> Neg for Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn neg(self) -> Matrix<T> {
+            -&self
+        }
+    }
+
+    // Scalars: either an entry (e.g. a Frac for Matrix<Frac>) or a plain i32
+    impl<T: Field> Mul<T> for &Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn mul(self, k: T) -> Matrix<T> {
+            self.scale(k)
+        }
+    }
+
+    impl<T: Field> Mul<T> for Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn mul(self, k: T) -> Matrix<T> {
+            self.scale(k)
+        }
+    }
+
+    impl<T: Field> Mul<i32> for &Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn mul(self, k: i32) -> Matrix<T> {
+            self.scale(T::from_i32(k))
+        }
+    }
+
+    impl<T: Field> Mul<i32> for Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn mul(self, k: i32) -> Matrix<T> {
+            self.scale(T::from_i32(k))
+        }
+    }
+
+    impl<T: Field> Div<T> for &Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn div(self, k: T) -> Matrix<T> {
+            self.map(|elem| elem.div(k))
+        }
+    }
+
+    impl<T: Field> Div<T> for Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn div(self, k: T) -> Matrix<T> {
+            self.map(|elem| elem.div(k))
+        }
+    }
+
+    impl<T: Field> Div<i32> for &Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn div(self, k: i32) -> Matrix<T> {
+            self / T::from_i32(k)
+        }
+    }
+
+    impl<T: Field> Div<i32> for Matrix<T> {
+        type Output = Matrix<T>;
+
+        fn div(self, k: i32) -> Matrix<T> {
+            self / T::from_i32(k)
+        }
+    }
+
+    impl<'b, T: Field> AddAssign<&'b Matrix<T>> for Matrix<T> {
+        fn add_assign(&mut self, other: &'b Matrix<T>) {
+            *self = unwrap_op(self.try_add(other));
+        }
+    }
+
+    impl<T: Field> AddAssign<Matrix<T>> for Matrix<T> {
+        fn add_assign(&mut self, other: Matrix<T>) {
+            *self += &other;
+        }
+    }
+
+    impl<'b, T: Field> SubAssign<&'b Matrix<T>> for Matrix<T> {
+        fn sub_assign(&mut self, other: &'b Matrix<T>) {
+            *self = unwrap_op(self.try_sub(other));
+        }
+    }
+
+    impl<T: Field> SubAssign<Matrix<T>> for Matrix<T> {
+        fn sub_assign(&mut self, other: Matrix<T>) {
+            *self -= &other;
+        }
+    }
+
+    impl<'b, T: Field> MulAssign<&'b Matrix<T>> for Matrix<T> {
+        fn mul_assign(&mut self, other: &'b Matrix<T>) {
+            *self = unwrap_op(self.try_mul(other));
+        }
+    }
+
+    impl<T: Field> MulAssign<Matrix<T>> for Matrix<T> {
+        fn mul_assign(&mut self, other: Matrix<T>) {
+            *self *= &other;
+        }
+    }
+
+    impl<T: Field> MulAssign<T> for Matrix<T> {
+        fn mul_assign(&mut self, k: T) {
+            for elem in self.data.iter_mut() {
+                *elem = elem.mul(k);
+            }
+        }
+    }
+
+    impl<T: Field> MulAssign<i32> for Matrix<T> {
+        fn mul_assign(&mut self, k: i32) {
+            *self *= T::from_i32(k);
+        }
+    }
+
+    impl<T: Field> DivAssign<T> for Matrix<T> {
+        fn div_assign(&mut self, k: T) {
+            for elem in self.data.iter_mut() {
+                *elem = elem.div(k);
+            }
+        }
+    }
+
+    impl<T: Field> DivAssign<i32> for Matrix<T> {
+        fn div_assign(&mut self, k: i32) {
+            *self /= T::from_i32(k);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::ops::{Add, Mul};
+        use fracs::Frac;
+        use mats::{Matrix, MatrixError};
+        use mats::format::Silent;
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn operators_borrow_their_operands() {
+            let (a, b, c) = (matr("1, 2; 3, 4"), matr("0, 1; 1, 0"), matr("1, 1; 1, 1"));
+            assert_eq!(&a * &b + &c, matr("3, 2; 5, 4"));
+            assert_eq!(&a - &c, matr("0, 1; 2, 3"));
+            assert_eq!(-&a, matr("-1, -2; -3, -4"));
+            // Method syntax finds the std::ops impls rather than anything that consumes `a`
+            assert_eq!((&a).add(&b), matr("1, 3; 4, 4"));
+            assert_eq!((&a).mul(&b), &a * &b);
+            assert_eq!(a, matr("1, 2; 3, 4"));
+        }
+
+        #[test]
+        fn scalars_and_assignment() {
+            let a = matr("1, 2; 3, 4");
+            assert_eq!(&a * 2, matr("2, 4; 6, 8"));
+            assert_eq!(&a * Frac::new(1, 2), &a / 2);
+            assert_eq!(&a / Frac::new(1, 3), matr("3, 6; 9, 12"));
+            let mut b = a.clone();
+            b += &a;
+            b -= matr("1, 0; 0, 1");
+            b *= &matr("0, 1; 1, 0");
+            b *= 3;
+            b /= Frac::from(6);
+            assert_eq!(b, matr("2, 1/2; 7/2, 3"));
+        }
+
+        #[test]
+        fn try_methods_report_mismatched_dimensions() {
+            let (a, b) = (matr("1, 2, 3"), matr("1, 2; 3, 4"));
+            let mismatch = |op| MatrixError::DimensionMismatch { left: (1, 3), right: (2, 2), op };
+            assert_eq!(a.try_add(&b), Err(mismatch("addition")));
+            assert_eq!(a.try_sub(&b), Err(mismatch("subtraction")));
+            assert_eq!(a.try_mul(&b), Err(mismatch("multiplication")));
+            assert_eq!(a.try_div(&b, &mut Silent), Err(mismatch("division")));
+            assert_eq!(b.try_mul(&matr("1; 1")), Ok(matr("3; 7")));
+        }
+
+        #[test]
+        fn division_solves_for_the_left_factor() {
+            let (a, b) = (matr("1, 2; 3, 4"), matr("2, 0; 1, 1"));
+            let product = &a * &b;
+            assert_eq!(product.try_div(&b, &mut Silent), Ok(a));
+            let ones = matr("1, 1; 1, 1");
+            assert_eq!(b.try_div(&ones, &mut Silent), Err(MatrixError::NoSolution { rhs: 0 }));
+            assert_eq!(matr("2, 2; 1, 1").try_div(&ones, &mut Silent), Err(MatrixError::NotUnique { free_vars: vec![1] }));
+        }
+
+        #[test]
+        #[should_panic]
+        fn operators_panic_on_mismatched_dimensions() {
+            let _ = &matr("1, 2") + &matr("1; 2");
+        }
+    }
+}
+
+pub mod steps {
+    use std::fmt;
+    use field::Field;
+    use mats::Matrix;
+    use mats::format::*;
+
+    // One elementary row operation. Rows are stored 0-based like everywhere else in the code, but
+    // displayed 1-based to match how they're written by hand.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum RowOp<T: Field> {
+        Swap(usize, usize),
+        Scale { row: usize, by: T },
+        AddMultiple { target: usize, source: usize, by: T },
+        // target ← scale * target + by * source, for cross-multiplying without fractions
+        Combine { target: usize, source: usize, scale: T, by: T }
+    }
+
+    impl<T: Field> fmt::Display for RowOp<T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                RowOp::Swap(a, b) => write!(f, "R{} ↔ R{}", a + 1, b + 1),
+                RowOp::Scale { row, by } => write!(f, "({}) * R{} → R{1}", by, row + 1),
+                RowOp::AddMultiple { target, source, by } => {
+                    if by.is_one() {
+                        write!(f, "R{} + R{} → R{0}", target + 1, source + 1)
+                    } else if by.negative().is_one() {
+                        write!(f, "R{} - R{} → R{0}", target + 1, source + 1)
+                    } else if by.is_negative() {
+                        write!(f, "R{} - ({}) * R{} → R{0}", target + 1, by.negative(), source + 1)
+                    } else {
+                        write!(f, "R{} + ({}) * R{} → R{0}", target + 1, by, source + 1)
+                    }
+                },
+                RowOp::Combine { target, source, scale, by } => {
+                    if scale.is_one() {
+                        return write!(f, "{}", RowOp::AddMultiple { target, source, by });
+                    }
+                    write!(f, "({}) * R{} ", scale, target + 1)?;
+                    if by.is_one() {
+                        write!(f, "+ R{}", source + 1)?;
+                    } else if by.negative().is_one() {
+                        write!(f, "- R{}", source + 1)?;
+                    } else if by.is_negative() {
+                        write!(f, "- ({}) * R{}", by.negative(), source + 1)?;
+                    } else {
+                        write!(f, "+ ({}) * R{}", by, source + 1)?;
+                    }
+                    write!(f, " → R{}", target + 1)
+                }
+            }
+        }
+    }
+
+    impl<T: Field> RowOp<T> {
+        pub fn apply(&self, matr: &mut Matrix<T>) {
+            match *self {
+                RowOp::Swap(a, b) => matr.swap_rows(a, b),
+                RowOp::Scale { row, by } => {
+                    for elem in matr.row_slice_mut(row).iter_mut() {
+                        *elem = elem.mul(by);
+                    }
+                },
+                RowOp::AddMultiple { target, source, by } => {
+                    if target == source {
+                        return RowOp::Scale { row: target, by: T::one().add(by) }.apply(matr);
+                    }
+                    let (target, tool) = matr.row_pair_mut(target, source);
+                    for (elem, &t) in target.iter_mut().zip(tool) {
+                        *elem = elem.add(by.mul(t));
+                    }
+                },
+                RowOp::Combine { target, source, scale, by } => {
+                    if target == source {
+                        return RowOp::Scale { row: target, by: scale.add(by) }.apply(matr);
+                    }
+                    let (target, tool) = matr.row_pair_mut(target, source);
+                    for (elem, &t) in target.iter_mut().zip(tool) {
+                        *elem = scale.mul(*elem).add(by.mul(t));
+                    }
+                }
+            }
+        }
+
+        // The operation that undoes this one. Scaling by zero can't be undone, so that panics.
+        pub fn inverse(&self) -> RowOp<T> {
+            match *self {
+                RowOp::Swap(a, b) => RowOp::Swap(a, b),
+                RowOp::Scale { row, by } => RowOp::Scale { row, by: by.inverse() },
+                RowOp::AddMultiple { target, source, by } => RowOp::AddMultiple { target, source, by: by.negative() },
+                RowOp::Combine { target, source, scale, by } => {
+                    RowOp::Combine { target, source, scale: scale.inverse(), by: by.div(scale).negative() }
+                }
+            }
+        }
+
+        pub fn scalar(&self) -> Option<T> {
+            match *self {
+                RowOp::Swap(..) => None,
+                RowOp::Scale { by, .. } | RowOp::AddMultiple { by, .. } | RowOp::Combine { by, .. } => Some(by)
+            }
+        }
+
+        // The row that changes. A swap changes two; this gives the first.
+        pub fn target(&self) -> usize {
+            match *self {
+                RowOp::Swap(a, _) => a,
+                RowOp::Scale { row, .. } => row,
+                RowOp::AddMultiple { target, .. } | RowOp::Combine { target, .. } => target
+            }
+        }
+    }
+
+    // A row operation along with the matrix as it looked right after. `augmented` is the right-hand
+    // half when the same operations are being applied to two matrices at once, like in `inverse`.
+    #[derive(Clone, Debug)]
+    pub struct Step<T: Field> {
+        pub op: RowOp<T>,
+        pub matrix: Matrix<T>,
+        pub augmented: Option<Matrix<T>>
+    }
+
+    impl<T: Field> fmt::Display for Step<T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.augmented {
+                Some(ref aug) => write!(f, "{}\n{}", self.op, add_mat_to_string(self.matrix.to_string(), aug, Separator::Space)),
+                None => write!(f, "{}\n{}", self.op, self.matrix)
+            }
+        }
+    }
+
+    // Collects steps instead of printing them. Plain text like the "Starting REF" banners is
+    // dropped.
+    pub struct StepLog<T: Field> {
+        pub steps: Vec<Step<T>>
+    }
+
+    impl<T: Field> StepLog<T> {
+        pub fn new() -> Self {
+            StepLog {
+                steps: Vec::new()
+            }
+        }
+    }
+
+    impl<T: Field> Default for StepLog<T> {
+        fn default() -> Self {
+            StepLog::new()
+        }
+    }
+
+    impl<T: Field> StepSink<T> for StepLog<T> {
+        fn emit(&mut self, _text: &str) {}
+
+        fn is_enabled(&self) -> bool {
+            false
+        }
+
+        fn record(&mut self, step: &Step<T>) {
+            self.steps.push(step.clone());
+        }
+
+        fn wants_steps(&self) -> bool {
+            true
+        }
+    }
+
+    // Runs the operations from a step list over a starting matrix, e.g. to check a log or to apply
+    // the same reduction to another matrix
+    pub fn replay<T: Field>(start: &Matrix<T>, steps: &[Step<T>]) -> Matrix<T> {
+        let mut matr = start.clone();
+        for step in steps {
+            step.op.apply(&mut matr);
+        }
+        matr
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use fracs::Frac;
+        use mats::Matrix;
+        use mats::steps::*;
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn row_ops_display_one_based() {
+            assert_eq!(RowOp::Swap::<Frac>(0, 1).to_string(), "R1 ↔ R2");
+            assert_eq!(RowOp::Scale { row: 0, by: Frac::new(1, 2) }.to_string(), "(1 / 2) * R1 → R1");
+            assert_eq!(RowOp::AddMultiple { target: 1, source: 0, by: Frac::from(1) }.to_string(), "R2 + R1 → R2");
+            assert_eq!(RowOp::AddMultiple { target: 1, source: 0, by: Frac::from(-3) }.to_string(), "R2 - (3) * R1 → R2");
+            let combine = RowOp::Combine { target: 2, source: 0, scale: Frac::from(2), by: Frac::from(-3) };
+            assert_eq!(combine.to_string(), "(2) * R3 - (3) * R1 → R3");
+            assert_eq!(combine.scalar(), Some(Frac::from(-3)));
+            assert_eq!(combine.target(), 2);
+            assert_eq!(RowOp::Swap::<Frac>(1, 0).scalar(), None);
+        }
+
+        #[test]
+        fn inverse_ops_undo_the_originals() {
+            let start = matr("1, 2; 3, 4; 5, 6");
+            let ops = [
+                RowOp::Swap(0, 2),
+                RowOp::Scale { row: 1, by: Frac::new(-2, 3) },
+                RowOp::AddMultiple { target: 2, source: 1, by: Frac::from(4) },
+                RowOp::Combine { target: 0, source: 1, scale: Frac::from(3), by: Frac::from(-1) }
+            ];
+            let mut a = start.clone();
+            RowOp::AddMultiple { target: 1, source: 0, by: Frac::from(-3) }.apply(&mut a);
+            assert_eq!(a, matr("1, 2; 0, -2; 5, 6"));
+            for op in &ops {
+                let mut b = start.clone();
+                op.apply(&mut b);
+                assert!(b != start);
+                op.inverse().apply(&mut b);
+                assert_eq!(b, start);
+            }
+        }
+
+        #[test]
+        fn logged_steps_replay_to_the_result() {
+            let start = matr("0, 2, 4; 1, 1, 1; 2, 0, 3");
+            let mut a = start.clone();
+            let steps = a.reduced_row_echelon_form_steps();
+            assert_eq!(steps[0].op, RowOp::Swap(0, 1));
+            assert_eq!(replay(&start, &steps), a);
+            assert_eq!(steps.last().unwrap().matrix, a);
+            assert_eq!(a, Matrix::identity(3));
+
+            // The same operations turn I into the inverse
+            let (inv, steps) = start.inverse_steps().unwrap();
+            assert_eq!(replay(&Matrix::identity(3), &steps), inv);
+            assert_eq!(steps.last().unwrap().augmented, Some(inv.clone()));
+            assert_eq!(&start * &inv, Matrix::identity(3));
+        }
+    }
+}
+
+pub mod transforms {
+    use fracs;
+    use field::{Field, Ring};
+    use mats::{Matrix, MatrixError};
+    use mats::format::*;
+    use mats::steps::{RowOp, Step, StepLog};
+    use swap::{FirstNonzero, PivotStrategy, SimplestPivot};
+
+    // Where elimination found its pivots. `pivots` holds (row, col) pairs from top to bottom, and
+    // every column without a pivot is listed in `free_columns`.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct PivotInfo {
+        pub pivots: Vec<(usize, usize)>,
+        pub rank: usize,
+        pub free_columns: Vec<usize>
+    }
+
+    impl PivotInfo {
+        pub fn pivot_columns(&self) -> Vec<usize> {
+            self.pivots.iter().map(|&(_, col)| col).collect()
+        }
+
+        pub fn is_pivot_column(&self, col: usize) -> bool {
+            self.pivots.iter().any(|&(_, c)| c == col)
+        }
+    }
+
+    // What `echelon_pass` keeps for an LU factorization: the multiplier used on each row under a
+    // pivot, stored where L needs it, and where every row of the original ended up
+    pub(super) struct Multipliers<T: Field> {
+        pub l: Matrix<T>,
+        pub perm: Vec<usize>,
+        pub swaps: usize
+    }
+
+    impl<T: Field> Multipliers<T> {
+        pub fn new(height: usize) -> Self {
+            Multipliers {
+                l: Matrix::identity(height),
+                perm: (0..height).collect(),
+                swaps: 0
+            }
+        }
+
+        // Only the multipliers already found move with the rows
+        fn swap(&mut self, row: usize, other: usize) {
+            self.perm.swap(row, other);
+            for c in 0..row {
+                let tmp = self.l.at(row, c);
+                *self.l.at_mut(row, c) = self.l.at(other, c);
+                *self.l.at_mut(other, c) = tmp;
+            }
+            self.swaps += 1;
+        }
+    }
+
+    impl<T: Field> Matrix<T> {
+        // Wrapper functions for convenience
+        pub fn row_ops_add(&mut self, target_row: usize, tool: usize) {
+            RowOp::AddMultiple { target: target_row, source: tool, by: T::one() }.apply(self);
+        }
+
+        pub fn row_ops_sub(&mut self, target_row: usize, tool: usize) {
+            RowOp::AddMultiple { target: target_row, source: tool, by: T::one().negative() }.apply(self);
+        }
+
+        pub fn row_ops_mul(&mut self, target_row: usize, amt: T) {
+            RowOp::Scale { row: target_row, by: amt }.apply(self);
+        }
+
+        pub fn row_ops_div(&mut self, target_row: usize, amt: T) {
+            RowOp::Scale { row: target_row, by: amt.inverse() }.apply(self);
+        }
+
+        pub fn row_ops_swap(&mut self, row1: usize, row2: usize) {
+            RowOp::Swap(row1, row2).apply(self);
+        }
+
+        // Applies the operation to this matrix and to the augmented half, if there is one, then
+        // hands the step to the sink
+        fn do_step(&mut self, op: RowOp<T>, augmented: &mut Option<&mut Matrix<T>>, sink: &mut dyn StepSink<T>) {
+            op.apply(self);
+            if let Some(aug) = augmented.as_mut() {
+                op.apply(aug);
+            }
+            if sink.wants_steps() {
+                sink.record(&Step {
+                    op,
+                    matrix: self.clone(),
+                    augmented: augmented.as_ref().map(|aug| (**aug).clone())
+                });
+            }
+        }
+
+        // The REF half of elimination, shared by `row_echelon_form`, `inverse` and `plu`. Works column
+        // by column, so zero columns are skipped instead of knocking every later pivot off the
+        // diagonal. Only the columns of `self` are searched for pivots - the augmented half just
+        // follows along. With `multipliers`, pivot rows are left unscaled and the multipliers are
+        // kept instead of thrown away.
+        pub(super) fn echelon_pass(&mut self, strategy: &dyn PivotStrategy<T>, augmented: &mut Option<&mut Matrix<T>>,
+                                   multipliers: &mut Option<&mut Multipliers<T>>, sink: &mut dyn StepSink<T>) -> PivotInfo {
+            let (mut pivots, mut free_columns) = (Vec::new(), Vec::new());
+            let mut row = 0;
+            for col in 0..self.width {
+                if row == self.height {
+                    free_columns.push(col);
+                    continue;
+                }
+                // Let the strategy pick a row at or beneath the current one with a value in this column
+                let other = match strategy.choose(self, col, row) {
+                    Some(other) => other,
+                    None => { // Nothing to pivot on - the column is free
+                        free_columns.push(col);
+                        continue;
+                    }
+                };
+                if other != row {
+                    self.do_step(RowOp::Swap(row, other), augmented, sink);
+                    if let Some(lu) = multipliers.as_mut() {
+                        lu.swap(row, other);
+                    }
+                }
+                let amt1 = self.at(row, col);
+                if !amt1.is_one() && multipliers.is_none() {
+                    self.do_step(RowOp::Scale { row, by: amt1.inverse() }, augmented, sink);
+                }
+                let pivot = self.at(row, col);
+                for below in row + 1..self.height {
+                    let amt = self.at(below, col);
+                    if !amt.is_zero() {
+                        let factor = amt.div(pivot);
+                        if let Some(lu) = multipliers.as_mut() {
+                            *lu.l.at_mut(below, row) = factor;
+                        }
+                        self.do_step(RowOp::AddMultiple { target: below, source: row, by: factor.negative() }, augmented, sink);
+                    }
+                }
+                pivots.push((row, col));
+                row += 1;
+            }
+            PivotInfo {
+                rank: pivots.len(),
+                pivots,
+                free_columns
+            }
+        }
+
+        // Clears everything above each pivot, working up from the last one
+        fn reduce_pass(&mut self, info: &PivotInfo, augmented: &mut Option<&mut Matrix<T>>, sink: &mut dyn StepSink<T>) {
+            for &(prow, pcol) in info.pivots.iter().rev() {
+                for above in (0..prow).rev() {
+                    let amt = self.at(above, pcol);
+                    if !amt.is_zero() {
+                        self.do_step(RowOp::AddMultiple { target: above, source: prow, by: amt.negative() }, augmented, sink);
+                    }
+                }
+            }
+        }
+
+        pub fn row_echelon_form(&mut self, sink: &mut dyn StepSink<T>) -> PivotInfo {
+            self.row_echelon_form_with(&FirstNonzero, sink)
+        }
+
+        pub fn reduced_row_echelon_form(&mut self, sink: &mut dyn StepSink<T>) -> PivotInfo {
+            self.reduced_row_echelon_form_with(&FirstNonzero, sink)
+        }
+
+        // Same as above, with the rule for choosing pivot rows picked by the caller
+        pub fn row_echelon_form_with(&mut self, strategy: &dyn PivotStrategy<T>, sink: &mut dyn StepSink<T>) -> PivotInfo {
+            if sink.is_enabled() {
+                sink.emit("------- Starting REF -------\n\n");
+            }
+            self.echelon_pass(strategy, &mut None, &mut None, sink)
+        }
+
+        pub fn reduced_row_echelon_form_with(&mut self, strategy: &dyn PivotStrategy<T>, sink: &mut dyn StepSink<T>) -> PivotInfo {
+            let info = self.row_echelon_form_with(strategy, sink);
+            if sink.is_enabled() {
+                sink.emit("------- Completed REF, starting RREF -------\n\n");
+            }
+            self.reduce_pass(&info, &mut None, sink);
+            info
+        }
+
+        // Same as above, but returns the operations instead of printing them
+        pub fn row_echelon_form_steps(&mut self) -> Vec<Step<T>> {
+            let mut log = StepLog::new();
+            self.row_echelon_form(&mut log);
+            log.steps
+        }
+
+        pub fn reduced_row_echelon_form_steps(&mut self) -> Vec<Step<T>> {
+            let mut log = StepLog::new();
+            self.reduced_row_echelon_form(&mut log);
+            log.steps
+        }
+
+        // The inverse can be achieved by taking a matrix and transforming it into a unit matrix (RREF
+        // form) and applying the transformations to a unit matrix. The resulting non-unit matrix is the
+        // inverse of the original. This runs the same REF and RREF passes as above with the unit matrix
+        // riding along as the augmented half.
+        pub fn inverse(&self, sink: &mut dyn StepSink<T>) -> Result<Matrix<T>, MatrixError> {
+            self.inverse_with(&FirstNonzero, sink)
+        }
+
+        pub fn inverse_with(&self, strategy: &dyn PivotStrategy<T>, sink: &mut dyn StepSink<T>) -> Result<Matrix<T>, MatrixError> {
+            let mut slef = self.clone();
+            if slef.height != slef.width {
+                return Err(MatrixError::NotSquare { dimension: slef.dimension() });
+            }
+            let mut unit = Matrix::identity(slef.height);
+            if sink.is_enabled() {
+                sink.emit(&format!("Setup at start of inverse calculation:\n{}\n\n", add_mat_to_string(slef.to_string(), &unit, Separator::Space)));
+            }
+            let info = slef.echelon_pass(strategy, &mut Some(&mut unit), &mut None, sink);
+            // Any column without a pivot means the matrix can't be reduced to the unit matrix
+            if let Some(&col) = info.free_columns.first() {
+                return Err(MatrixError::Singular { pivot_col: col });
+            }
+            slef.reduce_pass(&info, &mut Some(&mut unit), sink);
+            Ok(unit)
+        }
+
+        // The inverse along with every step taken to get it. Each step's `augmented` matrix is the
+        // right-hand half of [A | I].
+        pub fn inverse_steps(&self) -> Result<(Matrix<T>, Vec<Step<T>>), MatrixError> {
+            let mut log = StepLog::new();
+            let inv = self.inverse(&mut log)?;
+            Ok((inv, log.steps))
+        }
+    }
+
+    // Content of a list of fractions: the gcd of the numerators over the lcm of the denominators,
+    // so dividing by it leaves coprime integers. None if everything's zero or the lcm won't fit.
+    fn content(entries: &[fracs::Frac]) -> Option<fracs::Frac> {
+        let (mut num_gcd, mut den_lcm) = (0u32, 1i64);
+        for elem in entries.iter().filter(|elem| elem.num != 0) {
+            let elem = elem.try_simplify();
+            num_gcd = fracs::get_gcd(num_gcd, elem.num.unsigned_abs());
+            let den = elem.den as i64;
+            den_lcm = den_lcm / fracs::get_gcd(den_lcm as u32, den as u32) as i64 * den;
+            if den_lcm > i32::MAX as i64 {
+                return None;
+            }
+        }
+        if num_gcd == 0 {
+            return None;
+        }
+        Some(fracs::Frac::new(num_gcd as i32, den_lcm as i32))
+    }
+
+    // Simplification only makes sense for fractions - it divides rows through by their content,
+    // turning them into integer rows with no common factor
+    impl Matrix {
+        pub fn try_simplify_matrix(&mut self, sink: &mut dyn StepSink) {
+            if sink.is_enabled() {
+                sink.emit("Attempting to simplify matrix.\n\n");
+            }
+            let mut did_simplification = false;
+            for row in 0..self.height {
+                if self.try_simplify(row, sink) {
+                    did_simplification = true;
+                }
+            }
+            if !sink.is_enabled() {
+                return;
+            }
+            if did_simplification {
+                sink.emit(&format!("\nWas able to simplify. New matrix:\n\n{}\n\n", self));
+            } else {
+                sink.emit("\nWas unable to simplify.\n\n");
+            }
+        }
+
+        // Divides the row by its content, flipping the sign too if every nonzero entry is negative.
+        // Returns whether anything changed.
+        pub fn try_simplify(&mut self, row: usize, sink: &mut dyn StepSink) -> bool {
+            let row_vec = self.row_slice(row).to_vec();
+            let mut by = match content(&row_vec) {
+                Some(content) => content.inverse(),
+                None => return false
+            };
+            if row_vec.iter().all(|elem| elem.num == 0 || elem.is_negative()) {
+                by = by.negative();
+            }
+            if by.is_one() {
+                return false;
+            }
+            self.do_step(RowOp::Scale { row, by }, &mut None, sink);
+            true
+        }
+
+        pub fn row_ops_try_simplify(&mut self, row: usize, sink: &mut dyn StepSink) {
+            self.try_simplify(row, sink);
+        }
+
+        // The whole matrix divided by the content of its entries, with the first nonzero entry made
+        // positive. Mostly for basis vectors, so (1/2, -1/3) comes out as (3, -2).
+        pub fn integer_scaled(&self) -> Matrix {
+            let flat = self.to_flat();
+            let mut by = match content(&flat) {
+                Some(content) => content.inverse(),
+                None => return self.clone()
+            };
+            if flat.iter().find(|elem| elem.num != 0).is_some_and(|elem| elem.is_negative()) {
+                by = by.negative();
+            }
+            self.map(|elem| elem.mul(by))
+        }
+
+        // Integer-preserving elimination. Rows are cleared of fractions and common factors first,
+        // then a row with q under a pivot p becomes p * R - q * R_pivot (both divided by gcd(p, q))
+        // and gets simplified again, so every intermediate row stays integral. Pivots are only
+        // divided out at the very end.
+        pub fn integer_row_echelon_form(&mut self, sink: &mut dyn StepSink) -> PivotInfo {
+            self.integer_row_echelon_form_with(&SimplestPivot, sink)
+        }
+
+        pub fn integer_reduced_row_echelon_form(&mut self, sink: &mut dyn StepSink) -> PivotInfo {
+            self.integer_reduced_row_echelon_form_with(&SimplestPivot, sink)
+        }
+
+        pub fn integer_row_echelon_form_with(&mut self, strategy: &dyn PivotStrategy<fracs::Frac>, sink: &mut dyn StepSink) -> PivotInfo {
+            let info = self.integer_echelon_pass(strategy, sink);
+            self.normalize_pivots(&info, sink);
+            info
+        }
+
+        pub fn integer_reduced_row_echelon_form_with(&mut self, strategy: &dyn PivotStrategy<fracs::Frac>,
+                                                     sink: &mut dyn StepSink) -> PivotInfo {
+            let info = self.integer_echelon_pass(strategy, sink);
+            if sink.is_enabled() {
+                sink.emit("------- Completed REF, starting RREF -------\n\n");
+            }
+            for &(prow, pcol) in info.pivots.iter().rev() {
+                for above in (0..prow).rev() {
+                    if self.at(above, pcol).num != 0 {
+                        self.cross_eliminate(above, prow, pcol, sink);
+                    }
+                }
+            }
+            self.normalize_pivots(&info, sink);
+            info
+        }
+
+        fn integer_echelon_pass(&mut self, strategy: &dyn PivotStrategy<fracs::Frac>, sink: &mut dyn StepSink) -> PivotInfo {
+            if sink.is_enabled() {
+                sink.emit("------- Starting REF -------\n\n");
+            }
+            for row in 0..self.height {
+                self.try_simplify(row, sink);
+            }
+            let (mut pivots, mut free_columns) = (Vec::new(), Vec::new());
+            let mut row = 0;
+            for col in 0..self.width {
+                if row == self.height {
+                    free_columns.push(col);
+                    continue;
+                }
+                let other = match strategy.choose(self, col, row) {
+                    Some(other) => other,
+                    None => {
+                        free_columns.push(col);
+                        continue;
+                    }
+                };
+                if other != row {
+                    self.do_step(RowOp::Swap(row, other), &mut None, sink);
+                }
+                for below in row + 1..self.height {
+                    if self.at(below, col).num != 0 {
+                        self.cross_eliminate(below, row, col, sink);
+                    }
+                }
+                pivots.push((row, col));
+                row += 1;
+            }
+            PivotInfo {
+                rank: pivots.len(),
+                pivots,
+                free_columns
+            }
+        }
+
+        // Clears `col` in the target row using the source row without introducing fractions
+        fn cross_eliminate(&mut self, target: usize, source: usize, col: usize, sink: &mut dyn StepSink) {
+            let (mut p, mut q) = (self.at(source, col), self.at(target, col));
+            if let Some(g) = content(&[p, q]) {
+                p = p.div(g);
+                q = q.div(g);
+            }
+            // Keep the multiplier on the target row positive so the step reads naturally
+            if p.is_negative() {
+                p = p.negative();
+                q = q.negative();
+            }
+            self.do_step(RowOp::Combine { target, source, scale: p, by: q.negative() }, &mut None, sink);
+            self.try_simplify(target, sink);
+        }
+
+        fn normalize_pivots(&mut self, info: &PivotInfo, sink: &mut dyn StepSink) {
+            if sink.is_enabled() {
+                sink.emit("------- Normalizing pivots -------\n\n");
+            }
+            for &(prow, pcol) in info.pivots.iter() {
+                let amt = self.at(prow, pcol);
+                if !amt.is_one() {
+                    self.do_step(RowOp::Scale { row: prow, by: amt.inverse() }, &mut None, sink);
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use mats::Matrix;
+        use mats::format::Silent;
+        use mats::steps::StepLog;
+        use mats::transforms::PivotInfo;
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn zero_columns_are_skipped() {
+            let mut a = matr("0, 1, 2; 0, 2, 4; 0, 3, 7");
+            let info = a.reduced_row_echelon_form(&mut Silent);
+            assert_eq!(info, PivotInfo { pivots: vec![(0, 1), (1, 2)], rank: 2, free_columns: vec![0] });
+            assert_eq!(a, matr("0, 1, 0; 0, 0, 1; 0, 0, 0"));
+        }
+
+        #[test]
+        fn wide_and_tall_matrices() {
+            let mut wide = matr("1, 2, 3, 4; 2, 4, 7, 9");
+            let info = wide.row_echelon_form(&mut Silent);
+            assert_eq!(wide, matr("1, 2, 3, 4; 0, 0, 1, 1"));
+            assert_eq!(info.pivot_columns(), vec![0, 2]);
+            assert_eq!(info.free_columns, vec![1, 3]);
+            assert!(info.is_pivot_column(2) && !info.is_pivot_column(3));
+            wide.reduced_row_echelon_form(&mut Silent);
+            assert_eq!(wide, matr("1, 2, 0, 1; 0, 0, 1, 1"));
+
+            let mut tall = matr("2; 4; 6");
+            let info = tall.reduced_row_echelon_form(&mut Silent);
+            assert_eq!(info, PivotInfo { pivots: vec![(0, 0)], rank: 1, free_columns: vec![] });
+            assert_eq!(tall, matr("1; 0; 0"));
+        }
+
+        #[test]
+        fn zero_and_empty_matrices() {
+            let mut zero: Matrix = Matrix::zeros(2, 3);
+            let info = zero.reduced_row_echelon_form(&mut Silent);
+            assert_eq!(info, PivotInfo { pivots: vec![], rank: 0, free_columns: vec![0, 1, 2] });
+            let mut empty: Matrix = Matrix::zeros(0, 0);
+            assert_eq!(empty.reduced_row_echelon_form(&mut Silent).rank, 0);
+        }
+
+        #[test]
+        fn integer_elimination_cross_multiplies() {
+            let start = matr("2, 3, 1; 3, 4, 5; 5, 2, 7");
+            let mut log = StepLog::new();
+            let mut a = start.clone();
+            a.integer_row_echelon_form(&mut log);
+            let ops: Vec<String> = log.steps.iter().map(|step| step.op.to_string()).collect();
+            assert_eq!(ops[..4], ["(2) * R2 - (3) * R1 → R2", "(2) * R3 - (5) * R1 → R3", "R3 - (11) * R2 → R3", "(-1 / 68) * R3 → R3"]);
+            // Nothing but integers until the pivots are normalized
+            assert!(log.steps[..4].iter().all(|step| step.matrix.iter().all(|(_, elem)| elem.den == 1)));
+            assert_eq!(a, matr("1, 3/2, 1/2; 0, 1, -7; 0, 0, 1"));
+
+            let mut b = start.clone();
+            b.integer_reduced_row_echelon_form(&mut Silent);
+            assert_eq!(b, Matrix::identity(3));
+        }
+
+        #[test]
+        fn rows_simplify_to_coprime_integers() {
+            let mut a = matr("2, 4, -6; -1/2, -1/3, 0; 1, 2, 3; 0, 0, 0");
+            assert!(a.try_simplify(0, &mut Silent));
+            assert!(a.try_simplify(1, &mut Silent));
+            assert!(!a.try_simplify(2, &mut Silent));
+            assert!(!a.try_simplify(3, &mut Silent));
+            assert_eq!(a, matr("1, 2, -3; 3, 2, 0; 1, 2, 3; 0, 0, 0"));
+
+            assert_eq!(matr("1/2, -1/3").integer_scaled(), matr("3, -2"));
+            assert_eq!(matr("0, -1/2, 1/3").integer_scaled(), matr("0, 3, -2"));
+            assert_eq!(matr("0; 0").integer_scaled(), matr("0; 0"));
+        }
+    }
+}
+
+pub mod determinants {
+    use field::Field;
+    use mats::{Matrix, MatrixError};
+    use mats::format::*;
+    use mats::steps::{RowOp, Step};
+
+    impl<T: Field> Matrix<T> {
+        pub fn determinant(&self) -> Result<T, MatrixError> {
+            self.bareiss_determinant(&mut Silent)
+        }
+
+        // Bareiss fraction-free elimination. Each stage replaces the rows under pivot p with
+        // (p * R_i - q * R_k) / p_prev, where p_prev is the previous pivot. The division is always
+        // exact, so integer matrices stay integral all the way through, and the last pivot is the
+        // determinant. Row swaps flip the sign.
+        // Fractions are cleared first by scaling each row up to integers, and the scales divided
+        // back out of the result. If a scale won't fit, this falls back to plain LU elimination.
+        pub fn bareiss_determinant(&self, sink: &mut dyn StepSink<T>) -> Result<T, MatrixError> {
+            if self.height != self.width {
+                return Err(MatrixError::NotSquare { dimension: self.dimension() });
+            }
+            let n = self.height;
+            if n == 0 {
+                return Ok(T::one());
+            }
+            let scales = match (0..n).map(|i| T::integer_scale(self.row_slice(i))).collect::<Option<Vec<T>>>() {
+                Some(scales) => scales,
+                None => {
+                    if sink.is_enabled() {
+                        sink.emit("Clearing the fractions would overflow, so eliminating with them instead.\n\n");
+                    }
+                    return self.plu().determinant_from_lu();
+                }
+            };
+            let mut slef = self.clone();
+            if sink.is_enabled() {
+                sink.emit(&format!("------- Bareiss determinant -------\n\n{}\n\n", slef));
+            }
+            for (row, &by) in scales.iter().enumerate() {
+                if !by.is_one() {
+                    record_step(&mut slef, RowOp::Scale { row, by }, sink);
+                }
+            }
+            let (mut prev, mut negated) = (T::one(), false);
+            for k in 0..n - 1 {
+                if slef.at(k, k).is_zero() {
+                    let other = match (k + 1..n).find(|&i| !slef.at(i, k).is_zero()) {
+                        Some(other) => other,
+                        None => { // A zero column below the diagonal - the determinant's zero
+                            if sink.is_enabled() {
+                                sink.emit(&format!("Column {} has no pivot, so the determinant is 0.\n\n", k + 1));
+                            }
+                            return Ok(T::zero());
+                        }
+                    };
+                    record_step(&mut slef, RowOp::Swap(k, other), sink);
+                    negated = !negated;
+                }
+                let pivot = slef.at(k, k);
+                for i in k + 1..n {
+                    let amt = slef.at(i, k);
+                    if amt.is_zero() && prev == pivot {
+                        continue; // Nothing to do
+                    }
+                    // Written as a row operation this is scale * R_i + by * R_k with both divided by
+                    // the previous pivot
+                    let op = RowOp::Combine { target: i, source: k, scale: pivot.div(prev), by: amt.negative().div(prev) };
+                    record_step(&mut slef, op, sink);
+                }
+                prev = pivot;
+            }
+            let last = slef.at(n - 1, n - 1);
+            let det = if negated { last.negative() } else { last };
+            if sink.is_enabled() {
+                if negated {
+                    sink.emit(&format!("Odd number of row swaps, so det = -({}) = {}\n\n", last, det));
+                } else {
+                    sink.emit(&format!("det = {}\n\n", det));
+                }
+            }
+            Ok(unscale(det, &scales, sink))
+        }
+    }
+
+    // Which line to expand along in `determinant_by_cofactors`. Auto picks the row or column with
+    // the most zeros, preferring rows and lower indices on ties.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Expansion {
+        Row(usize),
+        Col(usize),
+        Auto
+    }
+
+    impl<T: Field> Matrix<T> {
+        // The matrix left after deleting row i and column j
+        pub fn minor_matrix(&self, i: usize, j: usize) -> Matrix<T> {
+            if i >= self.height || j >= self.width {
+                panic!("Can't remove row {}, column {} from a {}x{} matrix.", i, j, self.height, self.width);
+            }
+            Matrix::from_fn(self.height - 1, self.width - 1, |r, c| {
+                self.at(if r < i { r } else { r + 1 }, if c < j { c } else { c + 1 })
+            })
+        }
+
+        pub fn minor(&self, i: usize, j: usize) -> Result<T, MatrixError> {
+            if self.height != self.width {
+                return Err(MatrixError::NotSquare { dimension: self.dimension() });
+            }
+            self.minor_matrix(i, j).determinant()
+        }
+
+        pub fn cofactor(&self, i: usize, j: usize) -> Result<T, MatrixError> {
+            let minor = self.minor(i, j)?;
+            Ok(if (i + j).is_multiple_of(2) { minor } else { minor.negative() })
+        }
+
+        pub fn cofactor_matrix(&self) -> Result<Matrix<T>, MatrixError> {
+            if self.height != self.width {
+                return Err(MatrixError::NotSquare { dimension: self.dimension() });
+            }
+            let mut ret = Matrix::from_dimension(self.dimension());
+            for i in 0..self.height {
+                for j in 0..self.width {
+                    *ret.at_mut(i, j) = self.cofactor(i, j)?;
+                }
+            }
+            Ok(ret)
+        }
+
+        // The transpose of the cofactor matrix, so that A * adj(A) = det(A) * I
+        pub fn adjugate(&self) -> Result<Matrix<T>, MatrixError> {
+            Ok(self.cofactor_matrix()?.transpose())
+        }
+
+        // A⁻¹ = adj(A) / det(A). Much slower than `inverse` for anything big, but it's the formula
+        // usually taught for 2x2 and 3x3 matrices.
+        pub fn inverse_via_adjugate(&self) -> Result<Matrix<T>, MatrixError> {
+            let det = self.determinant()?;
+            if det.is_zero() {
+                let info = self.clone().row_echelon_form(&mut Silent);
+                return Err(MatrixError::Singular { pivot_col: info.free_columns.first().cloned().unwrap_or(0) });
+            }
+            let inv = det.inverse();
+            Ok(self.adjugate()?.map(|elem| elem.mul(inv)))
+        }
+
+        // Laplace expansion, printing each level as the matrix next to its expansion in terms of
+        // minors. The top level uses `expansion`; every minor below it expands along its line with
+        // the most zeros.
+        pub fn determinant_by_cofactors(&self, expansion: Expansion, sink: &mut dyn StepSink<T>) -> Result<T, MatrixError> {
+            if self.height != self.width {
+                return Err(MatrixError::NotSquare { dimension: self.dimension() });
+            }
+            match expansion {
+                Expansion::Row(i) | Expansion::Col(i) if i >= self.height => {
+                    panic!("Can't expand along line {} of a {}x{} matrix.", i, self.height, self.width);
+                },
+                _ => {}
+            }
+            Ok(self.expand_cofactors(expansion, sink))
+        }
+
+        fn expand_cofactors(&self, expansion: Expansion, sink: &mut dyn StepSink<T>) -> T {
+            let n = self.height;
+            if n == 0 {
+                return T::one();
+            }
+            if n == 1 {
+                return self.at(0, 0);
+            }
+            if n == 2 {
+                let (a, b, c, d) = (self.at(0, 0), self.at(0, 1), self.at(1, 0), self.at(1, 1));
+                let det = a.mul(d).sub(b.mul(c));
+                if sink.is_enabled() {
+                    let text = format!("({}) * ({}) - ({}) * ({}) = {}", a, d, b, c, det);
+                    sink.emit(&format!("{}\n\n", add_block_to_string(self.to_string(), &text, Separator::Equals)));
+                }
+                return det;
+            }
+            let (along_row, line) = match expansion {
+                Expansion::Row(i) => (true, i),
+                Expansion::Col(j) => (false, j),
+                Expansion::Auto => self.most_zeros()
+            };
+            // (row, col) of each entry on the line, skipping the zeros
+            let positions = (0..n).map(|k| if along_row { (line, k) } else { (k, line) })
+                .filter(|&(i, j)| !self.at(i, j).is_zero())
+                .collect::<Vec<(usize, usize)>>();
+            let terms = positions.iter().map(|&(i, j)| {
+                let coeff = if (i + j).is_multiple_of(2) { self.at(i, j) } else { self.at(i, j).negative() };
+                (coeff, self.minor_matrix(i, j))
+            }).collect::<Vec<(T, Matrix<T>)>>();
+            if sink.is_enabled() {
+                sink.emit(&format!("Expanding along {} {}:\n", if along_row { "row" } else { "column" }, line + 1));
+                let mut string = self.to_string();
+                if terms.is_empty() {
+                    string = add_block_to_string(string, "0", Separator::Equals);
+                }
+                for (k, &(coeff, ref minor)) in terms.iter().enumerate() {
+                    let (separator, shown) = if k == 0 {
+                        (Separator::Equals, coeff)
+                    } else if coeff.is_negative() {
+                        (Separator::Minus, coeff.negative())
+                    } else {
+                        (Separator::Plus, coeff)
+                    };
+                    string = add_block_to_string(string, &format!("({})", shown), separator);
+                    string = add_mat_to_string(string, minor, Separator::Times);
+                }
+                sink.emit(&format!("{}\n\n", string));
+            }
+            let mut det = T::zero();
+            for (coeff, minor) in terms {
+                det = det.add(coeff.mul(minor.expand_cofactors(Expansion::Auto, sink)));
+            }
+            if sink.is_enabled() {
+                sink.emit(&format!("{}\n\n", add_block_to_string(self.to_string(), &det.to_string(), Separator::Equals)));
+            }
+            det
+        }
+
+        // (true, row) or (false, col) for the line with the most zeros
+        fn most_zeros(&self) -> (bool, usize) {
+            let mut best = (true, 0, 0);
+            for line in 0..self.height {
+                let row_zeros = (0..self.width).filter(|&k| self.at(line, k).is_zero()).count();
+                if row_zeros > best.2 {
+                    best = (true, line, row_zeros);
+                }
+            }
+            for line in 0..self.width {
+                let col_zeros = (0..self.height).filter(|&k| self.at(k, line).is_zero()).count();
+                if col_zeros > best.2 {
+                    best = (false, line, col_zeros);
+                }
+            }
+            (best.0, best.1)
+        }
+    }
+
+    // Scaling a row scales the determinant, so divide each row scale back out
+    fn unscale<T: Field>(det: T, scales: &[T], sink: &mut dyn StepSink<T>) -> T {
+        if scales.iter().all(|scale| scale.is_one()) {
+            return det;
+        }
+        // One at a time so the running value stays as small as possible
+        let unscaled = scales.iter().fold(det, |acc, &scale| acc.div(scale));
+        if sink.is_enabled() {
+            let shown = scales.iter().filter(|scale| !scale.is_one()).map(|scale| format!("({})", scale))
+                .collect::<Vec<String>>().join(" * ");
+            sink.emit(&format!("Undoing the row scaling: {} / {} = {}\n\n", det, shown, unscaled));
+        }
+        unscaled
+    }
+
+    fn record_step<T: Field>(matr: &mut Matrix<T>, op: RowOp<T>, sink: &mut dyn StepSink<T>) {
+        op.apply(matr);
+        if sink.wants_steps() {
+            sink.record(&Step { op, matrix: matr.clone(), augmented: None });
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use field::ModInt;
+        use fracs::Frac;
+        use mats::{Matrix, MatrixError};
+        use mats::determinants::Expansion;
+        use mats::format::Silent;
+        use mats::steps::StepLog;
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn bareiss_known_determinants() {
+            assert_eq!(matr("2, -3, 1; 2, 0, -1; 1, 4, 5").determinant(), Ok(Frac::from(49)));
+            assert_eq!(matr("1/2, 1/3; 1/4, 1/5").determinant(), Ok(Frac::new(1, 60)));
+            assert_eq!(matr("1, 2; 2, 4").determinant(), Ok(Frac::from(0)));
+            assert_eq!(matr("0, 1; 0, 2").determinant(), Ok(Frac::from(0)));
+            assert_eq!(Matrix::<Frac>::zeros(0, 0).determinant(), Ok(Frac::from(1)));
+            assert_eq!(matr("1, 2, 3").determinant(), Err(MatrixError::NotSquare { dimension: (1, 3) }));
+            let z7 = |n| ModInt::<7>::from(n);
+            let a = Matrix::from_rows(vec![vec![z7(1), z7(2)], vec![z7(3), z7(4)]]).unwrap();
+            assert_eq!(a.determinant(), Ok(z7(5)));
+        }
+
+        #[test]
+        fn swaps_flip_the_sign() {
+            assert_eq!(matr("0, 1; 1, 0").determinant(), Ok(Frac::from(-1)));
+            assert_eq!(matr("0, 2, 1; 3, 1, 0; 1, 0, 2").determinant(), Ok(Frac::from(-13)));
+            let mut text = String::new();
+            assert_eq!(matr("0, 2, 1; 3, 1, 0; 1, 0, 2").bareiss_determinant(&mut text), Ok(Frac::from(-13)));
+            assert!(text.contains("R1 ↔ R2"));
+            assert!(text.contains("Odd number of row swaps, so det = -(13) = -13"));
+        }
+
+        #[test]
+        fn integer_matrices_stay_integral() {
+            let mut log = StepLog::new();
+            let a = matr("2, -3, 1; 2, 0, -1; 1, 4, 5");
+            assert_eq!(a.bareiss_determinant(&mut log), Ok(Frac::from(49)));
+            assert!(log.steps.iter().all(|step| step.matrix.iter().all(|(_, elem)| elem.den == 1)));
+            assert_eq!(log.steps.last().unwrap().matrix[(2, 2)], Frac::from(49));
+
+            let mut text = String::new();
+            matr("1/2, 1/3; 1/4, 1/5").bareiss_determinant(&mut text).unwrap();
+            assert!(text.contains("Undoing the row scaling: 2 / (6) * (20) = 1 / 60"));
+        }
+
+        #[test]
+        fn minors_cofactors_and_adjugate() {
+            let a = matr("2, -3, 1; 2, 0, -1; 1, 4, 5");
+            assert_eq!(a.minor_matrix(1, 2), matr("2, -3; 1, 4"));
+            assert_eq!(a.minor(0, 0), Ok(Frac::from(4)));
+            assert_eq!(a.cofactor(0, 1), Ok(Frac::from(-11)));
+            assert_eq!(a.cofactor_matrix(), Ok(matr("4, -11, 8; 19, 9, -11; 3, 4, 6")));
+            let adj = a.adjugate().unwrap();
+            assert_eq!(&a * &adj, Matrix::scalar(3, Frac::from(49)));
+            assert_eq!(a.inverse_via_adjugate(), a.inverse(&mut Silent));
+            assert_eq!(matr("1, 2; 2, 4").inverse_via_adjugate(), Err(MatrixError::Singular { pivot_col: 1 }));
+            assert_eq!(matr("1, 2").cofactor_matrix(), Err(MatrixError::NotSquare { dimension: (1, 2) }));
+        }
+
+        #[test]
+        fn cofactor_expansion_agrees_with_bareiss() {
+            let a = matr("2, -3, 1, 0; 2, 0, -1, 3; 1, 4, 5, -2; 0, 1, 1, 1");
+            let det = a.determinant().unwrap();
+            for expansion in [Expansion::Row(0), Expansion::Row(3), Expansion::Col(1), Expansion::Auto] {
+                assert_eq!(a.determinant_by_cofactors(expansion, &mut Silent), Ok(det));
+            }
+
+            let b = matr("1, 0, 2; 0, 3, 0; 4, 0, 5");
+            let mut text = String::new();
+            assert_eq!(b.determinant_by_cofactors(Expansion::Auto, &mut text), Ok(Frac::from(-9)));
+            assert!(text.starts_with("Expanding along row 2:\n"));
+            assert!(text.contains("(1) * (5) - (2) * (4) = -3"));
+            assert!(!text.contains("\\n"));
+        }
+    }
+}
+
+// Rank and the four fundamental subspaces, all read off the RREF. Bases come back as lists of
+// column vectors.
+pub mod subspaces {
+    use field::Field;
+    use mats::Matrix;
+    use mats::format::Silent;
+
+    impl<T: Field> Matrix<T> {
+        pub fn rank(&self) -> usize {
+            self.clone().row_echelon_form(&mut Silent).rank
+        }
+
+        pub fn nullity(&self) -> usize {
+            self.width - self.rank()
+        }
+
+        // One vector per free column: a one in that column, and minus the free column's RREF
+        // entries in the pivot columns
+        pub fn null_space(&self) -> Vec<Matrix<T>> {
+            let mut rref = self.clone();
+            let info = rref.reduced_row_echelon_form(&mut Silent);
+            info.free_columns.iter().map(|&free| {
+                let mut vector = Matrix::from_dimension((self.width, 1));
+                *vector.at_mut(free, 0) = T::one();
+                for &(prow, pcol) in info.pivots.iter() {
+                    *vector.at_mut(pcol, 0) = rref.at(prow, free).negative();
+                }
+                vector
+            }).collect()
+        }
+
+        // The original columns in the pivot positions
+        pub fn column_space(&self) -> Vec<Matrix<T>> {
+            let info = self.clone().row_echelon_form(&mut Silent);
+            info.pivot_columns().into_iter().map(|col| self.col(col)).collect()
+        }
+
+        // The nonzero rows of the RREF, stood up as columns
+        pub fn row_space(&self) -> Vec<Matrix<T>> {
+            let mut rref = self.clone();
+            let info = rref.reduced_row_echelon_form(&mut Silent);
+            (0..info.rank).map(|row| rref.row(row).transpose()).collect()
+        }
+
+        // Null space of the transpose: every y with yᵀA = 0
+        pub fn left_null_space(&self) -> Vec<Matrix<T>> {
+            self.transpose().null_space()
+        }
+    }
+
+    // Scales each vector of a fraction basis to coprime integers
+    pub fn integer_basis(basis: Vec<Matrix>) -> Vec<Matrix> {
+        basis.iter().map(Matrix::integer_scaled).collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use fracs::Frac;
+        use mats::Matrix;
+        use mats::subspaces::integer_basis;
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn rank_deficient_square_matrix() {
+            let a = matr("1, 2, 3; 2, 4, 6; 1, 0, 1");
+            assert_eq!((a.rank(), a.nullity()), (2, 1));
+            assert_eq!(a.null_space(), vec![matr("-1; -1; 1")]);
+            assert_eq!(a.column_space(), vec![matr("1; 2; 1"), matr("2; 4; 0")]);
+            assert_eq!(a.row_space(), vec![matr("1; 0; 1"), matr("0; 1; 1")]);
+            assert_eq!(a.left_null_space(), vec![matr("-2; 1; 0")]);
+            for v in a.null_space() {
+                assert_eq!(&a * &v, Matrix::zeros(3, 1));
+            }
+        }
+
+        #[test]
+        fn rectangular_matrices() {
+            let tall = matr("1, 2; 3, 4; 5, 6");
+            assert_eq!((tall.rank(), tall.nullity()), (2, 0));
+            assert!(tall.null_space().is_empty());
+            assert!(tall.is_linearly_independent());
+            assert!(!tall.transpose().is_linearly_independent());
+            assert_eq!(tall.left_null_space().len(), 1);
+
+            // Full row rank, but the middle column has no pivot
+            let wide = matr("1, 0, 0; 0, 0, 1");
+            assert_eq!(wide.rank(), 2);
+            assert!(!wide.is_linearly_independent());
+            assert_eq!(wide.null_space(), vec![matr("0; 1; 0")]);
+            assert_eq!(Matrix::<Frac>::zeros(2, 2).rank(), 0);
+        }
+
+        #[test]
+        fn integer_scaled_bases() {
+            let basis = matr("2, 3").null_space();
+            assert_eq!(basis, vec![matr("-3/2; 1")]);
+            assert_eq!(integer_basis(basis), vec![matr("3; -2")]);
+        }
+    }
+}
+
+// Solving Ax = b by row reducing the augmented matrix [A | b]
+pub mod systems {
+    use std::fmt;
+    use field::Field;
+    use mats::{Matrix, MatrixError};
+    use mats::format::*;
+
+    // Every vector here is a column. `free_vars` are 0-based column indices, matched up in order
+    // with `null_basis`, and the general solution is particular + t1 * null_basis[0] + ...
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Solution<T: Field> {
+        Unique(Matrix<T>),
+        Infinite { particular: Matrix<T>, null_basis: Vec<Matrix<T>>, free_vars: Vec<usize> },
+        // This row of the RREF reads 0 = 1
+        Inconsistent { row: usize }
+    }
+
+    // Names for the parameters, in order. Anything past these gets numbered.
+    const PARAMS: [&str; 6] = ["t", "s", "r", "u", "v", "w"];
+
+    fn param_name(k: usize, count: usize) -> String {
+        if count <= PARAMS.len() {
+            String::from(PARAMS[k])
+        } else {
+            format!("t{}", k + 1)
+        }
+    }
+
+    // Written out like "x1 = 2 - 3t, x2 = t"
+    impl<T: Field> fmt::Display for Solution<T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                Solution::Unique(ref x) => {
+                    let vars = x.to_flat().iter().enumerate().map(|(a, elem)| format!("x{} = {}", a + 1, elem)).collect::<Vec<String>>();
+                    write!(f, "{}", vars.join(", "))
+                },
+                Solution::Infinite { ref particular, ref null_basis, ref free_vars } => {
+                    let mut vars = Vec::with_capacity(particular.height);
+                    for a in 0..particular.height {
+                        if let Some(k) = free_vars.iter().position(|&free| free == a) {
+                            vars.push(format!("x{} = {}", a + 1, param_name(k, free_vars.len())));
+                            continue;
                         }
-                        continue;
-                    }
-                    if b == a { // On the diagonal line
-                        if amt1.num == 0 {
-                            let mut other: i32 = -1;
-                            // Find row beneath current one with a value in the columnn that the current
-                            // row's missing
-                            for i in (b..max).filter(|&i| i != a) {
-                                if slef.clone().matrix[i][b].num != 0 {
-                                    other = i as i32;
-                                    break;
-                                }
-                            }
-                            if other == -1 { // It's okay if there isn't one - just move on
+                        let constant = particular.at(a, 0);
+                        let mut expr = if constant.is_zero() { String::new() } else { constant.to_string() };
+                        for (k, vector) in null_basis.iter().enumerate() {
+                            let coeff = vector.at(a, 0);
+                            if coeff.is_zero() {
                                 continue;
                             }
-                            let other = other as usize;
-                            let mut add = true;
-                            let amt2 = slef.clone().matrix[other][b]; // Get second value
-                            match amt2.num > 0 {
-                                true => {
-                                    slef.row_ops_add(b, other); // Get value in zero element
-                                    unit.row_ops_add(b, other);
-                                }
-                                false => {
-                                    add = false;
-                                    slef.row_ops_sub(b, other); // Get value in zero element
-                                    unit.row_ops_sub(b, other);
-                                }
-                            }
-                            let sign = match add {
-                                true => String::from("+"),
-                                false => String::from("-")
+                            let magnitude = if coeff.is_negative() { coeff.negative() } else { coeff };
+                            let name = param_name(k, free_vars.len());
+                            let term = if magnitude.is_one() {
+                                name
+                            } else if magnitude.to_string().contains(' ') { // Fractions like "1 / 2"
+                                format!("({}){}", magnitude, name)
+                            } else {
+                                format!("{}{}", magnitude, name)
+                            };
+                            expr = match (expr.is_empty(), coeff.is_negative()) {
+                                (true, true) => format!("-{}", term),
+                                (true, false) => term,
+                                (false, true) => format!("{} - {}", expr, term),
+                                (false, false) => format!("{} + {}", expr, term)
                             };
-                            if print_steps {
-                                print!("R{} {} R{} → R{0}\n{}\n\n", a + 1, sign, other + 1,
-                                       add_mat_to_string(slef.to_string(), &unit, Separator::Space));
-                            }
-                            let amt1 = slef.clone().matrix[a][b]; // Refresh current value
-                            if amt1.num != 1 {
-                                slef.row_ops_div(a, amt1);
-                                if print_steps {
-                                    let foo = amt1.clone().inverse();
-                                    print!("({}) * R{} → R{1}\n{}\n\n", foo, a + 1,
-                                           add_mat_to_string(slef.to_string(), &unit, Separator::Space));
-                                }
-                            }
-                            continue;
                         }
-                        slef.row_ops_div(a, amt1); // Divide by slef
-                        unit.row_ops_div(a, amt1);
-                        if print_steps {
-                            let amt1 = amt1.inverse();
-                            print!("({}) * R{} → R{1}\n{}\n\n", amt1, a + 1,
-                                   add_mat_to_string(slef.to_string(), &unit, Separator::Space));
+                        if expr.is_empty() {
+                            expr = String::from("0");
                         }
-                        continue;
+                        vars.push(format!("x{} = {}", a + 1, expr));
+                    }
+                    write!(f, "{}", vars.join(", "))
+                },
+                Solution::Inconsistent { row } => write!(f, "No solution - row {} reduces to 0 = 1.", row + 1)
+            }
+        }
+    }
+
+    // Solutions to AX = B or XA = B. Each matrix in `homogeneous_basis` has the same shape as X
+    // and solves the equation with B = 0, so the general solution is particular + t1 * H1 + ....
+    // `free_vars` are the free columns of the system that was reduced: columns of A for AX = B,
+    // rows of A for XA = B.
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum MatrixSolution<T: Field> {
+        Unique(Matrix<T>),
+        Infinite { particular: Matrix<T>, homogeneous_basis: Vec<Matrix<T>>, free_vars: Vec<usize> },
+        // `row` of the reduced augmented matrix reads 0 = 1 for right-hand side `rhs` (column of B
+        // in AX = B, row of B in XA = B)
+        Inconsistent { row: usize, rhs: usize }
+    }
+
+    impl<T: Field> fmt::Display for MatrixSolution<T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                MatrixSolution::Unique(ref x) => write!(f, "{}", add_mat_to_string(String::from("X"), x, Separator::Equals)),
+                MatrixSolution::Infinite { ref particular, ref homogeneous_basis, .. } => {
+                    let mut string = add_mat_to_string(String::from("X"), particular, Separator::Equals);
+                    for (k, basis) in homogeneous_basis.iter().enumerate() {
+                        string = add_block_to_string(string, &param_name(k, homogeneous_basis.len()), Separator::Plus);
+                        string = add_mat_to_string(string, basis, Separator::Times);
+                    }
+                    write!(f, "{}", string)
+                },
+                MatrixSolution::Inconsistent { row, rhs } => {
+                    write!(f, "No solution - right-hand side {} leaves row {} reading 0 = 1.", rhs + 1, row + 1)
+                }
+            }
+        }
+    }
+
+    impl<T: Field> Matrix<T> {
+        // AX = B by reducing [A | B], with every column of B eliminated at once
+        pub fn solve_left(&self, b: &Matrix<T>, sink: &mut dyn StepSink<T>) -> Result<MatrixSolution<T>, MatrixError> {
+            if b.height != self.height {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: b.dimension(), op: "solve_left" });
+            }
+            let (n, p) = (self.width, b.width);
+            let mut augmented = Matrix::from_fn(self.height, n + p, |r, c| if c < n { self.at(r, c) } else { b.at(r, c - n) });
+            if sink.is_enabled() {
+                sink.emit(&format!("Augmented matrix:\n{}\n\n", add_mat_to_string(self.to_string(), b, Separator::Space)));
+            }
+            let info = augmented.reduced_row_echelon_form(sink);
+            if let Some(&(row, col)) = info.pivots.iter().find(|&&(_, col)| col >= n) {
+                return Ok(MatrixSolution::Inconsistent { row, rhs: col - n });
+            }
+            let mut particular = Matrix::from_dimension((n, p));
+            for &(prow, pcol) in info.pivots.iter() {
+                for c in 0..p {
+                    *particular.at_mut(pcol, c) = augmented.at(prow, n + c);
+                }
+            }
+            let free_vars = info.free_columns.iter().cloned().filter(|&col| col < n).collect::<Vec<usize>>();
+            if free_vars.is_empty() {
+                return Ok(MatrixSolution::Unique(particular));
+            }
+            // Each null vector of A can go in any one column of X
+            let mut homogeneous_basis = Vec::with_capacity(free_vars.len() * p);
+            for &free in free_vars.iter() {
+                for c in 0..p {
+                    let mut basis = Matrix::from_dimension((n, p));
+                    *basis.at_mut(free, c) = T::one();
+                    for &(prow, pcol) in info.pivots.iter() {
+                        *basis.at_mut(pcol, c) = augmented.at(prow, free).negative();
+                    }
+                    homogeneous_basis.push(basis);
+                }
+            }
+            Ok(MatrixSolution::Infinite { particular, homogeneous_basis, free_vars })
+        }
+
+        // XA = B, solved as AᵀXᵀ = Bᵀ and transposed back
+        pub fn solve_right(&self, b: &Matrix<T>, sink: &mut dyn StepSink<T>) -> Result<MatrixSolution<T>, MatrixError> {
+            if b.width != self.width {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: b.dimension(), op: "solve_right" });
+            }
+            Ok(match self.transpose().solve_left(&b.transpose(), sink)? {
+                MatrixSolution::Unique(x) => MatrixSolution::Unique(x.transpose()),
+                MatrixSolution::Infinite { particular, homogeneous_basis, free_vars } => MatrixSolution::Infinite {
+                    particular: particular.transpose(),
+                    homogeneous_basis: homogeneous_basis.iter().map(Matrix::transpose).collect(),
+                    free_vars
+                },
+                inconsistent => inconsistent
+            })
+        }
+
+        // b has to be a single column with as many rows as the matrix. The RREF steps of [A | b] go
+        // to the sink.
+        pub fn solve(&self, b: &Matrix<T>, sink: &mut dyn StepSink<T>) -> Result<Solution<T>, MatrixError> {
+            if b.width != 1 || b.height != self.height {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: b.dimension(), op: "solve" });
+            }
+            Ok(match self.solve_left(b, sink)? {
+                MatrixSolution::Unique(x) => Solution::Unique(x),
+                MatrixSolution::Infinite { particular, homogeneous_basis, free_vars } => {
+                    Solution::Infinite { particular, null_basis: homogeneous_basis, free_vars }
+                },
+                MatrixSolution::Inconsistent { row, .. } => Solution::Inconsistent { row }
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use mats::{Matrix, MatrixError};
+        use mats::format::Silent;
+        use mats::systems::Solution;
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn unique_solution() {
+            let mut text = String::new();
+            let x = matr("1, 1; 1, -1").solve(&matr("3; 1"), &mut text).unwrap();
+            assert_eq!(x, Solution::Unique(matr("2; 1")));
+            assert_eq!(x.to_string(), "x1 = 2, x2 = 1");
+            assert!(text.starts_with("Augmented matrix:\n"));
+        }
+
+        #[test]
+        fn parametric_solutions() {
+            let x = matr("1, 3").solve(&matr("2"), &mut Silent).unwrap();
+            assert_eq!(x, Solution::Infinite { particular: matr("2; 0"), null_basis: vec![matr("-3; 1")], free_vars: vec![1] });
+            assert_eq!(x.to_string(), "x1 = 2 - 3t, x2 = t");
+            let x = matr("2, 1").solve(&matr("1"), &mut Silent).unwrap();
+            assert_eq!(x.to_string(), "x1 = 1 / 2 - (1 / 2)t, x2 = t");
+            let x = matr("1, 1, 1").solve(&matr("0"), &mut Silent).unwrap();
+            assert_eq!(x.to_string(), "x1 = -t - s, x2 = t, x3 = s");
+        }
+
+        #[test]
+        fn inconsistent_and_malformed_systems() {
+            let a = matr("1, 1; 1, 1");
+            let x = a.solve(&matr("1; 2"), &mut Silent).unwrap();
+            assert_eq!(x, Solution::Inconsistent { row: 1 });
+            assert_eq!(x.to_string(), "No solution - row 2 reduces to 0 = 1.");
+            assert_eq!(a.solve(&matr("1, 2; 3, 4"), &mut Silent),
+                       Err(MatrixError::DimensionMismatch { left: (2, 2), right: (2, 2), op: "solve" }));
+            assert_eq!(a.solve(&matr("1"), &mut Silent),
+                       Err(MatrixError::DimensionMismatch { left: (2, 2), right: (1, 1), op: "solve" }));
+        }
+    }
+}
+
+// Exact factorizations. Everything stays in the entry field, so fraction matrices factor with no
+// rounding and no square roots.
+pub mod decompositions {
+    use field::Field;
+    use mats::{Matrix, MatrixError};
+    use mats::format::Silent;
+    use mats::transforms::Multipliers;
+    use swap::{FirstNonzero, PivotStrategy};
+
+    // PA = LU, with P a permutation matrix, L unit lower triangular and U in row echelon form (upper
+    // triangular when A is square). `perm[i]` is the row of A that ended up in row i.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Plu<T: Field> {
+        pub p: Matrix<T>,
+        pub l: Matrix<T>,
+        pub u: Matrix<T>,
+        pub perm: Vec<usize>,
+        pub swaps: usize
+    }
+
+    impl<T: Field> Matrix<T> {
+        pub fn plu(&self) -> Plu<T> {
+            self.plu_with(&FirstNonzero)
+        }
+
+        // Same elimination as `row_echelon_form`, except pivot rows aren't scaled and the
+        // multipliers are kept in L instead of thrown away
+        pub fn plu_with(&self, strategy: &dyn PivotStrategy<T>) -> Plu<T> {
+            let mut u = self.clone();
+            let mut lu = Multipliers::new(self.height);
+            u.echelon_pass(strategy, &mut None, &mut Some(&mut lu), &mut Silent);
+            u.compact();
+            Plu {
+                p: Matrix::permutation(&lu.perm).expect("perm only ever has rows swapped"),
+                l: lu.l,
+                u,
+                perm: lu.perm,
+                swaps: lu.swaps
+            }
+        }
+    }
+
+    impl<T: Field> Plu<T> {
+        // Solves AX = B for every column of B at once with forward and back substitution. Needs A
+        // to be square and invertible.
+        pub fn solve_with_lu(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+            let n = self.u.height;
+            if self.u.width != n {
+                return Err(MatrixError::NotSquare { dimension: self.u.dimension() });
+            }
+            if b.height != n {
+                return Err(MatrixError::DimensionMismatch { left: self.u.dimension(), right: b.dimension(), op: "solve_with_lu" });
+            }
+            if let Some(col) = (0..n).find(|&a| self.u.at(a, a).is_zero()) {
+                return Err(MatrixError::Singular { pivot_col: col });
+            }
+            let mut x = Matrix::from_dimension(b.dimension());
+            for c in 0..b.width {
+                // Ly = Pb
+                let mut y = vec![T::zero(); n];
+                for a in 0..n {
+                    y[a] = (0..a).fold(b.at(self.perm[a], c), |acc, k| acc.sub(self.l.at(a, k).mul(y[k])));
+                }
+                // Ux = y
+                for a in (0..n).rev() {
+                    let sum = (a + 1..n).fold(y[a], |acc, k| acc.sub(self.u.at(a, k).mul(x.at(k, c))));
+                    *x.at_mut(a, c) = sum.div(self.u.at(a, a));
+                }
+            }
+            Ok(x)
+        }
+
+        // det(A) = ±(product of U's diagonal), negated for an odd number of swaps
+        pub fn determinant_from_lu(&self) -> Result<T, MatrixError> {
+            if self.u.height != self.u.width {
+                return Err(MatrixError::NotSquare { dimension: self.u.dimension() });
+            }
+            let det = (0..self.u.height).fold(T::one(), |acc, a| acc.mul(self.u.at(a, a)));
+            Ok(if self.swaps % 2 == 1 { det.negative() } else { det })
+        }
+    }
+
+    // PAPᵀ = LDLᵀ for a symmetric matrix, with L unit lower triangular and D block diagonal.
+    // `blocks` lists the sizes of D's diagonal blocks from the top: 1 for an ordinary pivot, 2 for a
+    // 2x2 pivot [[a, b], [b, c]] used when every remaining diagonal entry is zero.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Ldlt<T: Field> {
+        pub p: Matrix<T>,
+        pub l: Matrix<T>,
+        pub d: Matrix<T>,
+        pub perm: Vec<usize>,
+        pub blocks: Vec<usize>
+    }
+
+    impl<T: Field> Matrix<T> {
+        pub fn is_symmetric(&self) -> bool {
+            self.check_symmetric().is_ok()
+        }
+
+        fn check_symmetric(&self) -> Result<(), MatrixError> {
+            if self.height != self.width {
+                return Err(MatrixError::NotSquare { dimension: self.dimension() });
+            }
+            for a in 0..self.height {
+                for b in a + 1..self.width {
+                    if self.at(a, b) != self.at(b, a) {
+                        return Err(MatrixError::NotSymmetric { row: a, col: b });
                     }
                 }
             }
-            for a in (0..max - 1).rev() {
-                for b in (a + 1..max).rev() {
-                    let amt = slef.clone().matrix[a][b];
-                    if !amt.cmp(&fracs::Frac::from(0)).eq(&fracs::CmpRes::Eq) {
-                        slef.row_ops_mul(b, amt);
-                        unit.row_ops_mul(b, amt);
-                        slef.row_ops_sub(a, b);
-                        unit.row_ops_sub(a, b);
-                        slef.row_ops_div(b, amt);
-                        unit.row_ops_div(b, amt);
-                        if print_steps {
-                            print!("R{} - ({}) * R{} → R{0}\n{}\n\n", a + 1, amt, b + 1,
-                                   add_mat_to_string(slef.to_string(), &unit, Separator::Space))
+            Ok(())
+        }
+
+        // Pivots are picked for exactness rather than size, Bunch–Kaufman style: a nonzero diagonal
+        // entry if there is one (swapped into place symmetrically), otherwise a 2x2 block around an
+        // off-diagonal nonzero. A column that's all zero just gives a zero in D.
+        pub fn ldlt(&self) -> Result<Ldlt<T>, MatrixError> {
+            self.check_symmetric()?;
+            let n = self.height;
+            let mut s = self.clone(); // What's left to factor lives in s[k.., k..]
+            s.compact();
+            let mut l = Matrix::identity(n);
+            let mut d = Matrix::from_dimension((n, n));
+            let mut perm = (0..n).collect::<Vec<usize>>();
+            let mut blocks = Vec::new();
+            let mut k = 0;
+            while k < n {
+                if s.at(k, k).is_zero() {
+                    if let Some(j) = (k + 1..n).find(|&j| !s.at(j, j).is_zero()) {
+                        symmetric_swap(&mut s, &mut l, &mut perm, k, j);
+                    } else if let Some(j) = (k + 1..n).find(|&j| !s.at(j, k).is_zero()) {
+                        symmetric_swap(&mut s, &mut l, &mut perm, k + 1, j);
+                        // 2x2 pivot E = [[a, b], [b, c]], invertible since a = c = 0 and b ≠ 0
+                        let (a, b, c) = (s.at(k, k), s.at(k + 1, k), s.at(k + 1, k + 1));
+                        let det = a.mul(c).sub(b.mul(b));
+                        let inv = [[c.div(det), b.negative().div(det)], [b.negative().div(det), a.div(det)]];
+                        for (r, c2, val) in [(k, k, a), (k, k + 1, b), (k + 1, k, b), (k + 1, k + 1, c)] {
+                            *d.at_mut(r, c2) = val;
+                        }
+                        for i in k + 2..n {
+                            let (x0, x1) = (s.at(i, k), s.at(i, k + 1));
+                            *l.at_mut(i, k) = x0.mul(inv[0][0]).add(x1.mul(inv[1][0]));
+                            *l.at_mut(i, k + 1) = x0.mul(inv[0][1]).add(x1.mul(inv[1][1]));
+                        }
+                        // Schur complement: s[i][j] -= [s_ik, s_ik+1] E⁻¹ [s_jk, s_jk+1]ᵀ
+                        for i in k + 2..n {
+                            for j in k + 2..n {
+                                let update = l.at(i, k).mul(s.at(j, k)).add(l.at(i, k + 1).mul(s.at(j, k + 1)));
+                                *s.at_mut(i, j) = s.at(i, j).sub(update);
+                            }
+                        }
+                        blocks.push(2);
+                        k += 2;
+                        continue;
+                    }
+                }
+                let pivot = s.at(k, k);
+                *d.at_mut(k, k) = pivot;
+                if !pivot.is_zero() {
+                    for i in k + 1..n {
+                        *l.at_mut(i, k) = s.at(i, k).div(pivot);
+                    }
+                    for i in k + 1..n {
+                        for j in k + 1..n {
+                            *s.at_mut(i, j) = s.at(i, j).sub(l.at(i, k).mul(s.at(k, j)));
                         }
                     }
                 }
+                blocks.push(1);
+                k += 1;
             }
-            for a in 0..max { // Check to see if the original matrix is now a unit matrix
-                for b in 0..max {
-                    if a != b && !slef.clone().matrix[b][a].cmp(&fracs::Frac::from(0)).eq(&fracs::CmpRes::Eq) {
-                        return Err(String::from("Unable to convert matrix into unit matrix to make the inverse."));
+            Ok(Ldlt {
+                p: Matrix::permutation(&perm).expect("perm only ever has rows swapped"),
+                l,
+                d,
+                perm,
+                blocks
+            })
+        }
+
+        // (positive, negative, zero) eigenvalue counts. By Sylvester's law of inertia these are the
+        // same for D as for the matrix, and D's blocks are small enough to read off directly.
+        // Needs an ordered field - see Ring::is_negative.
+        pub fn inertia(&self) -> Result<(usize, usize, usize), MatrixError> {
+            let ldlt = self.ldlt()?;
+            let (mut pos, mut neg, mut zero) = (0, 0, 0);
+            let mut k = 0;
+            for &size in ldlt.blocks.iter() {
+                if size == 1 {
+                    let val = ldlt.d.at(k, k);
+                    if val.is_zero() {
+                        zero += 1;
+                    } else if val.is_negative() {
+                        neg += 1;
+                    } else {
+                        pos += 1;
                     }
-                    if a == b && !slef.clone().matrix[b][a].cmp(&fracs::Frac::from(1)).eq(&fracs::CmpRes::Eq) {
-                        return Err(String::from("Unable to convert matrix into unit matrix to make the inverse."));
+                } else {
+                    let (a, b, c) = (ldlt.d.at(k, k), ldlt.d.at(k + 1, k), ldlt.d.at(k + 1, k + 1));
+                    let det = a.mul(c).sub(b.mul(b));
+                    if det.is_negative() { // Eigenvalues of opposite signs
+                        pos += 1;
+                        neg += 1;
+                    } else if a.add(c).is_negative() { // Same sign, and the trace says which
+                        neg += 2;
+                    } else {
+                        pos += 2;
                     }
                 }
+                k += size;
             }
-            Ok(unit)
+            Ok((pos, neg, zero))
+        }
+
+        pub fn is_positive_definite(&self) -> Result<bool, MatrixError> {
+            Ok(self.inertia()? == (self.height, 0, 0))
+        }
+
+        pub fn is_positive_semidefinite(&self) -> Result<bool, MatrixError> {
+            Ok(self.inertia()?.1 == 0)
+        }
+
+        pub fn is_negative_definite(&self) -> Result<bool, MatrixError> {
+            Ok(self.inertia()? == (0, self.height, 0))
+        }
+    }
+
+    // Swaps rows and columns i and j of what's left to factor, along with the parts of L already
+    // filled in
+    fn symmetric_swap<T: Field>(s: &mut Matrix<T>, l: &mut Matrix<T>, perm: &mut [usize], i: usize, j: usize) {
+        if i == j {
+            return;
+        }
+        s.swap_rows(i, j);
+        s.compact();
+        for a in 0..s.height {
+            let tmp = s.at(a, i);
+            *s.at_mut(a, i) = s.at(a, j);
+            *s.at_mut(a, j) = tmp;
+        }
+        for c in 0..i.min(j) {
+            let tmp = l.at(i, c);
+            *l.at_mut(i, c) = l.at(j, c);
+            *l.at_mut(j, c) = tmp;
+        }
+        perm.swap(i, j);
+    }
+
+    // Gram–Schmidt without normalizing: A = QR, where Q's columns are orthogonal and R is unit upper
+    // triangular. QᵀQ = D = diag(squared_norms), so normalizing later gives the usual
+    // A = (Q D^(-1/2)) (D^(1/2) R) in whatever field has the square roots. A column that depends on
+    // the ones before it gives a zero column in Q and a zero squared norm.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct GramSchmidt<T: Field> {
+        pub q: Matrix<T>,
+        pub r: Matrix<T>,
+        pub squared_norms: Vec<T>
+    }
+
+    fn dot<T: Field>(x: &[T], y: &[T]) -> T {
+        x.iter().zip(y).fold(T::zero(), |acc, (&a, &b)| acc.add(a.mul(b)))
+    }
+
+    impl<T: Field> Matrix<T> {
+        // q_j = a_j - Σ (q_i · a_j / q_i · q_i) q_i over the earlier columns, with the coefficients
+        // kept in R
+        pub fn gram_schmidt(&self) -> GramSchmidt<T> {
+            let n = self.width;
+            let cols = (0..n).map(|j| self.col(j).to_flat()).collect::<Vec<Vec<T>>>();
+            let mut qs: Vec<Vec<T>> = Vec::with_capacity(n);
+            let mut squared_norms = Vec::with_capacity(n);
+            let mut r = Matrix::identity(n);
+            for (j, col) in cols.iter().enumerate() {
+                let mut q = col.clone();
+                for (i, prev) in qs.iter().enumerate() {
+                    let norm: T = squared_norms[i];
+                    if norm.is_zero() {
+                        continue;
+                    }
+                    let coeff = dot(prev, col).div(norm);
+                    *r.at_mut(i, j) = coeff;
+                    for (elem, &p) in q.iter_mut().zip(prev) {
+                        *elem = elem.sub(coeff.mul(p));
+                    }
+                }
+                squared_norms.push(dot(&q, &q));
+                qs.push(q);
+            }
+            GramSchmidt {
+                q: Matrix::from_fn(self.height, n, |row, col| qs[col][row]),
+                r,
+                squared_norms
+            }
+        }
+
+        // Orthogonal projection of b onto the column space, Σ (q_i · b / q_i · q_i) q_i. Also the
+        // best fit Ax for least squares.
+        pub fn project_onto_column_space(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+            if b.width != 1 || b.height != self.height {
+                return Err(MatrixError::DimensionMismatch { left: self.dimension(), right: b.dimension(), op: "projection" });
+            }
+            let gs = self.gram_schmidt();
+            let b = b.to_flat();
+            let mut proj = vec![T::zero(); self.height];
+            for (j, &norm) in gs.squared_norms.iter().enumerate() {
+                if norm.is_zero() {
+                    continue;
+                }
+                let q = gs.q.col(j).to_flat();
+                let coeff = dot(&q, &b).div(norm);
+                for (elem, &p) in proj.iter_mut().zip(q.iter()) {
+                    *elem = elem.add(coeff.mul(p));
+                }
+            }
+            Matrix::from_flat(1, proj)
+        }
+    }
+}
+
+// Characteristic polynomials and what can be read off them exactly
+pub mod eigen {
+    use std::fmt;
+    use fracs::Frac;
+    use field::{Field, Ring};
+    use mats::{Matrix, MatrixError};
+    use poly::Polynomial;
+
+    // The rational eigenvalues with their algebraic multiplicities, smallest first. `remaining` is
+    // the characteristic polynomial with all of them divided out - its roots are irrational or
+    // complex.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct RationalEigenvalues {
+        pub eigenvalues: Vec<(Frac, usize)>,
+        pub remaining: Polynomial<Frac>
+    }
+
+    impl RationalEigenvalues {
+        pub fn remaining_degree(&self) -> usize {
+            self.remaining.degree().unwrap_or(0)
+        }
+    }
+
+    impl fmt::Display for RationalEigenvalues {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let roots = self.eigenvalues.iter().map(|&(root, multiplicity)| {
+                if multiplicity == 1 {
+                    format!("λ = {}", root)
+                } else {
+                    format!("λ = {} (multiplicity {})", root, multiplicity)
+                }
+            }).collect::<Vec<String>>();
+            if roots.is_empty() {
+                write!(f, "No rational eigenvalues")?;
+            } else {
+                write!(f, "{}", roots.join(", "))?;
+            }
+            if self.remaining_degree() > 0 {
+                write!(f, "; {} more from {}, with no rational roots", self.remaining_degree(), self.remaining)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl Matrix {
+        pub fn eigenvalues_rational(&self) -> Result<RationalEigenvalues, MatrixError> {
+            let (eigenvalues, remaining) = self.characteristic_polynomial()?.rational_roots();
+            Ok(RationalEigenvalues { eigenvalues, remaining })
+        }
+    }
+
+    impl<T: Field> Matrix<T> {
+        // det(λI - A). The products of entries along the way get much bigger than the answer, so
+        // this works in the wide version of the entries and only narrows the coefficients at the
+        // end.
+        pub fn characteristic_polynomial(&self) -> Result<Polynomial<T>, MatrixError> {
+            if self.height != self.width {
+                return Err(MatrixError::NotSquare { dimension: self.dimension() });
+            }
+            let n = self.height;
+            let coeffs = berkowitz(&self.map(T::widen)).into_iter().enumerate()
+                .map(|(k, coeff)| T::narrow(coeff).ok_or(MatrixError::CoefficientOverflow { power: n - k }))
+                .collect::<Result<Vec<T>, MatrixError>>()?;
+            Ok(Polynomial::new(coeffs))
+        }
+
+        // Null space of A - λI. Empty when λ isn't an eigenvalue.
+        pub fn eigenspace(&self, lambda: T) -> Result<Vec<Matrix<T>>, MatrixError> {
+            if self.height != self.width {
+                return Err(MatrixError::NotSquare { dimension: self.dimension() });
+            }
+            Ok((self - &Matrix::scalar(self.height, lambda)).null_space())
+        }
+
+        pub fn geometric_multiplicity(&self, lambda: T) -> Result<usize, MatrixError> {
+            Ok(self.eigenspace(lambda)?.len())
+        }
+
+        // Cayley–Hamilton: every square matrix satisfies its own characteristic polynomial, so
+        // p(A) should come out as the zero matrix
+        pub fn cayley_hamilton_check(&self) -> Result<bool, MatrixError> {
+            if self.height != self.width {
+                return Err(MatrixError::NotSquare { dimension: self.dimension() });
+            }
+            // Wide throughout - the powers of A overflow long before p(A) comes back to zero
+            let wide = self.map(T::widen);
+            let p = Polynomial::new(berkowitz(&wide));
+            Ok(p.eval_matrix(&wide)?.iter().all(|(_, elem)| elem.is_zero()))
+        }
+    }
+
+    // Berkowitz's algorithm, which never divides. Going from the leading k x k block A_k to
+    // A_(k+1) = [[A_k, c], [r, a]], the new polynomial is a lower triangular Toeplitz matrix with
+    // first column (1, -a, -rc, -rA_k c, ..., -rA_k^(k-1) c) times the old one. Coefficients come
+    // out highest degree first.
+    fn berkowitz<T: Field>(matr: &Matrix<T>) -> Vec<T> {
+        let mut coeffs = vec![T::one()];
+        for k in 0..matr.height {
+            let mut toeplitz = vec![T::one(), matr.at(k, k).negative()];
+            // v runs through c, A_k c, A_k^2 c, ...
+            let mut v = (0..k).map(|i| matr.at(i, k)).collect::<Vec<T>>();
+            for _ in 0..k {
+                let rv = (0..k).fold(T::zero(), |acc, j| acc.add(matr.at(k, j).mul(v[j])));
+                toeplitz.push(rv.negative());
+                v = (0..k).map(|i| (0..k).fold(T::zero(), |acc, j| acc.add(matr.at(i, j).mul(v[j])))).collect();
+            }
+            coeffs = (0..k + 2).map(|i| {
+                (0..coeffs.len()).filter(|&j| j <= i).fold(T::zero(), |acc, j| acc.add(toeplitz[i - j].mul(coeffs[j])))
+            }).collect();
+        }
+        coeffs
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use fracs::Frac;
+        use mats::{Matrix, MatrixError};
+
+        fn matr(string: &str) -> Matrix {
+            string.parse().unwrap()
+        }
+
+        #[test]
+        fn distinct_rational_eigenvalues() {
+            let a = matr("4, 1; 2, 3");
+            let found = a.eigenvalues_rational().unwrap();
+            assert_eq!(found.eigenvalues, vec![(Frac::from(2), 1), (Frac::from(5), 1)]);
+            assert_eq!(found.remaining_degree(), 0);
+            assert_eq!(found.to_string(), "λ = 2, λ = 5");
+            assert_eq!(a.eigenspace(Frac::from(5)), Ok(vec![matr("1; 1")]));
+            assert_eq!(a.eigenspace(Frac::from(2)), Ok(vec![matr("-1/2; 1")]));
+            assert_eq!(a.eigenspace(Frac::from(3)), Ok(vec![]));
+        }
+
+        #[test]
+        fn algebraic_and_geometric_multiplicities() {
+            let jordan = matr("2, 1; 0, 2");
+            let found = jordan.eigenvalues_rational().unwrap();
+            assert_eq!(found.eigenvalues, vec![(Frac::from(2), 2)]);
+            assert_eq!(found.to_string(), "λ = 2 (multiplicity 2)");
+            assert_eq!(jordan.geometric_multiplicity(Frac::from(2)), Ok(1));
+            assert_eq!(jordan.eigenspace(Frac::from(2)), Ok(vec![matr("1; 0")]));
+            assert_eq!(matr("2, 0; 0, 2").geometric_multiplicity(Frac::from(2)), Ok(2));
+        }
+
+        #[test]
+        fn irrational_and_complex_eigenvalues_are_left_over() {
+            let rotation = matr("0, -1; 1, 0").eigenvalues_rational().unwrap();
+            assert!(rotation.eigenvalues.is_empty());
+            assert_eq!(rotation.remaining_degree(), 2);
+            assert!(rotation.to_string().starts_with("No rational eigenvalues; 2 more from "));
+
+            // 2 and ±√2
+            let mixed = matr("2, 0, 0; 0, 0, 2; 0, 1, 0").eigenvalues_rational().unwrap();
+            assert_eq!(mixed.eigenvalues, vec![(Frac::from(2), 1)]);
+            assert_eq!(mixed.remaining_degree(), 2);
+            assert_eq!(matr("1, 2").eigenspace(Frac::from(1)), Err(MatrixError::NotSquare { dimension: (1, 2) }));
         }
     }
 }
 
 pub mod tests {
-    use std::cmp;
-    use fracs;
+    use field::Field;
     use mats::Matrix;
 
-    impl Matrix {
+    impl<T: Field> Matrix<T> {
+        // Whether the columns are linearly independent, i.e. every column has a pivot
         pub fn is_linearly_independent(&self) -> bool {
-            let mut tst = self.clone();
-            tst.row_echelon_form(false);
-            let max = cmp::min(self.dimension.0, self.dimension.1);
-            for a in 0..max {
-                for b in 0..a + 1 {
-                    if a != b && !tst.matrix[a][b].cmp(&fracs::Frac::from(0)).eq(&fracs::CmpRes::Eq) {
-                        return false;
-                    } else if a == b && !tst.matrix[a][b].cmp(&fracs::Frac::from(1)).eq(&fracs::CmpRes::Eq) {
-                        return false;
-                    }
+            self.rank() == self.width
+        }
+    }
+}
+
+// Round trips for the factorizations and solvers: multiplying the pieces back together has to give
+// the original matrix exactly
+#[cfg(test)]
+mod round_trips {
+    use fracs::Frac;
+    use mats::Matrix;
+    use mats::format::Silent;
+    use mats::systems::MatrixSolution;
+
+    fn matr(string: &str) -> Matrix {
+        string.parse().unwrap()
+    }
+
+    #[test]
+    fn ldlt_reassembles() {
+        let a = matr("4, 2, -2; 2, 5, 1; -2, 1, 6");
+        let f = a.ldlt().unwrap();
+        assert_eq!(&(&f.p * &a) * &f.p.transpose(), &(&f.l * &f.d) * &f.l.transpose());
+        assert_eq!(f.blocks, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn ldlt_reassembles_with_zero_diagonal() {
+        let a = matr("0, 1; 1, 0");
+        let f = a.ldlt().unwrap();
+        assert_eq!(f.blocks, vec![2]);
+        assert_eq!(&(&f.p * &a) * &f.p.transpose(), &(&f.l * &f.d) * &f.l.transpose());
+
+        let b = matr("0, 1, 2; 1, 0, 3; 2, 3, 0");
+        let f = b.ldlt().unwrap();
+        assert_eq!(&(&f.p * &b) * &f.p.transpose(), &(&f.l * &f.d) * &f.l.transpose());
+    }
+
+    #[test]
+    fn plu_reassembles() {
+        for a in &[matr("0, 2, 1; 1, 1, 1; 2, 0, 3"), matr("1, 2, 3; 2, 4, 6; 1, 0, 1"), matr("0, 1, 2, 3; 0, 2, 4, 7; 1, 1, 1, 1")] {
+            let f = a.plu();
+            assert_eq!(&f.p * a, &f.l * &f.u);
+        }
+    }
+
+    #[test]
+    fn gram_schmidt_reassembles() {
+        let a = matr("1, 1, 0; 1, 0, 1; 0, 1, 1; 1, 1, 1");
+        let f = a.gram_schmidt();
+        assert_eq!(&f.q * &f.r, a);
+        assert_eq!(&f.q.transpose() * &f.q, Matrix::diag(&f.squared_norms));
+    }
+
+    #[test]
+    fn cayley_hamilton() {
+        let a = matr("2, -1, 0; 1, 3, 4; 0, 5, 1/2");
+        let p = a.characteristic_polynomial().unwrap();
+        assert_eq!(p.eval_matrix(&a).unwrap(), Matrix::zeros(3, 3));
+        assert!(a.cayley_hamilton_check().unwrap());
+    }
+
+    // Berkowitz on the raw fractions used to overflow i32 here
+    #[test]
+    fn hilbert_characteristic_polynomial() {
+        let h: Matrix = Matrix::hilbert(4);
+        let p = h.characteristic_polynomial().unwrap();
+        assert_eq!(p.coeffs, vec![Frac::from(1), Frac::new(-176, 105), Frac::new(3341, 12600), Frac::new(-41, 23625),
+                                  Frac::new(1, 6048000)]);
+        assert!(h.cayley_hamilton_check().unwrap());
+    }
+
+    #[test]
+    fn solve_left_unique() {
+        let a = matr("2, 1, -1; -3, -1, 2; -2, 1, 2");
+        let b = matr("8, 1; -11, 0; -3, 2");
+        match a.solve_left(&b, &mut Silent).unwrap() {
+            MatrixSolution::Unique(x) => assert_eq!(&a * &x, b),
+            other => panic!("expected a unique solution, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn solve_left_infinite() {
+        let a = matr("1, 2, 3; 2, 4, 6; 1, 0, 1");
+        let b = matr("6; 12; 2");
+        match a.solve_left(&b, &mut Silent).unwrap() {
+            MatrixSolution::Infinite { particular, homogeneous_basis, .. } => {
+                assert_eq!(&a * &particular, b);
+                for h in &homogeneous_basis {
+                    assert_eq!(&a * h, Matrix::zeros(3, 1));
                 }
-            }
-            true
+            },
+            other => panic!("expected infinitely many solutions, got {:?}", other)
         }
     }
-}
\ No newline at end of file
+
+    // Bareiss on the raw fractions used to overflow i32 here
+    #[test]
+    fn hilbert_determinant() {
+        let h: Matrix = Matrix::hilbert(4);
+        assert_eq!(h.determinant().unwrap(), Frac::new(1, 6048000));
+        assert_eq!(h.determinant().unwrap(), h.plu().determinant_from_lu().unwrap());
+    }
+}
+
+#[cfg(test)]
+mod errors {
+    use std::error::Error;
+    use fracs::{Frac, WideFrac};
+    use mats::{Matrix, MatrixError, TSOpts};
+    use mats::format::Silent;
+
+    #[test]
+    fn construction_errors() {
+        let ragged = vec![vec![Frac::from(1), Frac::from(2)], vec![Frac::from(3)]];
+        assert_eq!(Matrix::from_vecs(ragged, TSOpts::default()),
+                   Err(MatrixError::RaggedRows { row: 1, expected: 2, found: 1 }));
+        assert_eq!(Matrix::from_i32_vec(2, vec![1, 2, 3], TSOpts::default()),
+                   Err(MatrixError::RaggedRows { row: 1, expected: 2, found: 1 }));
+        assert_eq!(Matrix::from_vecs(Vec::new(), TSOpts::default()), Err(MatrixError::Empty));
+        assert_eq!(Matrix::from_rows(vec![Vec::<Frac>::new()]), Err(MatrixError::Empty));
+        assert_eq!("1, 2; 3, x".parse::<Matrix>(), Err(MatrixError::Parse { row: 1, col: 1, token: String::from("x") }));
+    }
+
+    #[test]
+    fn algebra_errors() {
+        let a = "1, 2; 3, 4".parse::<Matrix>().unwrap();
+        assert_eq!(a.inverse(&mut Silent), Ok("-2, 1; 3/2, -1/2".parse().unwrap()));
+        assert_eq!("1, 2, 3".parse::<Matrix>().unwrap().inverse(&mut Silent),
+                   Err(MatrixError::NotSquare { dimension: (1, 3) }));
+        assert_eq!("1, 2; 2, 4".parse::<Matrix>().unwrap().inverse(&mut Silent),
+                   Err(MatrixError::Singular { pivot_col: 1 }));
+        let big = Matrix::from_rows(vec![vec![WideFrac::from(1), WideFrac::from(1 << 40)]]).unwrap();
+        assert_eq!(big.to_frac_matrix(), Err(MatrixError::Overflow { row: 0, col: 1 }));
+    }
+
+    #[test]
+    fn messages_are_one_based() {
+        let e: Box<dyn Error> = Box::new(MatrixError::Singular { pivot_col: 1 });
+        assert_eq!(e.to_string(), "Matrix is singular - there's no pivot in column 2.");
+        assert_eq!(MatrixError::DimensionMismatch { left: (1, 3), right: (2, 2), op: "addition" }.to_string(),
+                   "Matrices have incompatible dimensions for addition: 1x3 and 2x2.");
+        assert_eq!(MatrixError::RaggedRows { row: 1, expected: 2, found: 1 }.to_string(),
+                   "Row 2 has 1 entries, but the rows before it have 2.");
+        assert_eq!(MatrixError::Parse { row: 1, col: 1, token: String::from("x") }.to_string(),
+                   "Couldn't parse \"x\" at (2, 2).");
+    }
+}
@@ -0,0 +1,271 @@
+#![allow(dead_code)]
+
+// Polynomials in λ over any field, mainly for characteristic polynomials. Coefficients are stored
+// highest degree first, the same order they're written in and the order Matrix::companion takes.
+
+use std::fmt;
+
+use fracs::Frac;
+use field::Field;
+use mats::{Matrix, MatrixError};
+
+#[derive(Clone, PartialEq)]
+pub struct Polynomial<T: Field> {
+    pub coeffs: Vec<T>
+}
+
+impl<T: Field> Polynomial<T> {
+    // Leading zeros are dropped, so the zero polynomial has no coefficients at all
+    pub fn new(coeffs: Vec<T>) -> Self {
+        let start = coeffs.iter().position(|elem| !elem.is_zero()).unwrap_or(coeffs.len());
+        Polynomial {
+            coeffs: coeffs[start..].to_vec()
+        }
+    }
+
+    // None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    // Horner's rule
+    pub fn eval(&self, x: T) -> T {
+        self.coeffs.iter().fold(T::zero(), |acc, &c| acc.mul(x).add(c))
+    }
+
+    // Synthetic division by (λ - root), giving the quotient and the remainder p(root)
+    pub fn div_linear(&self, root: T) -> (Polynomial<T>, T) {
+        let mut quotient = Vec::with_capacity(self.coeffs.len());
+        let mut acc = T::zero();
+        for &c in self.coeffs.iter() {
+            acc = acc.mul(root).add(c);
+            quotient.push(acc);
+        }
+        let remainder = quotient.pop().unwrap_or(T::zero());
+        (Polynomial::new(quotient), remainder)
+    }
+
+    // p(A), also by Horner's rule
+    pub fn eval_matrix(&self, matr: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let (height, width) = matr.dimension();
+        if height != width {
+            return Err(MatrixError::NotSquare { dimension: matr.dimension() });
+        }
+        let mut ret = Matrix::zeros(height, width);
+        for &c in self.coeffs.iter() {
+            ret = ret.try_mul(matr)?;
+            ret += &Matrix::scalar(height, c);
+        }
+        Ok(ret)
+    }
+}
+
+// Written out like "λ^3 - 2λ + 1", with fractional coefficients in brackets
+impl<T: Field> fmt::Display for Polynomial<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let degree = self.coeffs.len() - 1;
+        let mut string = String::new();
+        for (k, &c) in self.coeffs.iter().enumerate() {
+            if c.is_zero() {
+                continue;
+            }
+            let power = degree - k;
+            let magnitude = if c.is_negative() { c.negative() } else { c };
+            let coeff = if power > 0 && magnitude.is_one() {
+                String::new()
+            } else if power > 0 && magnitude.to_string().contains(' ') { // Fractions like "1 / 2"
+                format!("({})", magnitude)
+            } else {
+                magnitude.to_string()
+            };
+            let var = match power {
+                0 => String::new(),
+                1 => String::from("λ"),
+                _ => format!("λ^{}", power)
+            };
+            string = match (string.is_empty(), c.is_negative()) {
+                (true, true) => format!("-{}{}", coeff, var),
+                (true, false) => format!("{}{}", coeff, var),
+                (false, true) => format!("{} - {}{}", string, coeff, var),
+                (false, false) => format!("{} + {}{}", string, coeff, var)
+            };
+        }
+        write!(f, "{}", string)
+    }
+}
+
+impl<T: Field> fmt::Debug for Polynomial<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Polynomial({})", self)
+    }
+}
+
+impl Polynomial<Frac> {
+    // Same roots, but with coprime integer coefficients. None if they don't fit in an i64.
+    pub fn integer_coeffs(&self) -> Option<Vec<i64>> {
+        let coeffs = self.coeffs.iter().map(|c| c.try_simplify()).collect::<Vec<Frac>>();
+        let lcm = coeffs.iter().try_fold(1i64, |acc, c| {
+            let den = c.den as i64;
+            (acc / int_gcd(acc, den)).checked_mul(den)
+        })?;
+        let ints = coeffs.iter().map(|c| (lcm / c.den as i64).checked_mul(c.num as i64)).collect::<Option<Vec<i64>>>()?;
+        let content = ints.iter().fold(0, |acc, &c| int_gcd(acc, c));
+        if content <= 1 {
+            return Some(ints);
+        }
+        Some(ints.iter().map(|&c| c / content).collect())
+    }
+
+    // Every rational root with its multiplicity, smallest first, along with what's left once
+    // they've all been divided out. By the rational root theorem a root p/q in lowest terms has p
+    // dividing the constant term and q dividing the leading coefficient, so there are only finitely
+    // many to try. If the coefficients are too big to clear denominators, no roots are found and
+    // the whole polynomial comes back untouched.
+    pub fn rational_roots(&self) -> (Vec<(Frac, usize)>, Polynomial<Frac>) {
+        let mut roots = Vec::new();
+        let mut rest = self.clone();
+        if rest.is_zero() {
+            return (roots, rest);
+        }
+        // Zero roots first, so the constant term below isn't zero
+        let zeros = rest.coeffs.iter().rev().take_while(|c| c.num == 0).count();
+        if zeros > 0 {
+            roots.push((Frac::from(0), zeros));
+            let len = rest.coeffs.len();
+            rest = Polynomial::new(rest.coeffs[..len - zeros].to_vec());
+        }
+        let ints = match rest.integer_coeffs() {
+            Some(ints) => ints,
+            None => return (Vec::new(), self.clone())
+        };
+        let (lead, constant) = (ints[0], ints[ints.len() - 1]);
+        // Anything bigger can't be a Frac numerator or denominator
+        let small = |d: &i64| *d <= i32::MAX as i64;
+        let qs = divisors(lead).into_iter().filter(small).collect::<Vec<i64>>();
+        let mut candidates = Vec::new();
+        for p in divisors(constant).into_iter().filter(small) {
+            for &q in qs.iter() {
+                if int_gcd(p, q) != 1 {
+                    continue;
+                }
+                for &p in [-p, p].iter() {
+                    if is_root(&ints, p, q) {
+                        candidates.push(Frac::new(p as i32, q as i32));
+                    }
+                }
+            }
+        }
+        for root in candidates {
+            let mut multiplicity = 0;
+            loop {
+                let (quotient, remainder) = rest.div_linear(root);
+                if remainder.num != 0 {
+                    break;
+                }
+                rest = quotient;
+                multiplicity += 1;
+            }
+            roots.push((root, multiplicity));
+        }
+        roots.sort_by(|a, b| (a.0.num as f64 / a.0.den as f64).partial_cmp(&(b.0.num as f64 / b.0.den as f64)).unwrap());
+        (roots, rest)
+    }
+}
+
+fn int_gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let c = b;
+        b = a % b;
+        a = c;
+    }
+    a
+}
+
+// Positive divisors by trial division up to the square root
+fn divisors(n: i64) -> Vec<i64> {
+    let n = n.abs();
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+    let mut d = 1;
+    while d <= n / d {
+        if n % d == 0 {
+            small.push(d);
+            if d != n / d {
+                large.push(n / d);
+            }
+        }
+        d += 1;
+    }
+    small.extend(large.into_iter().rev());
+    small
+}
+
+// Whether q^n * p(p/q) = Σ a_i p^(n-i) q^i is zero. Anything that overflows an i128 here couldn't be
+// divided out in 32 bit fractions anyway, so it's treated as not a root.
+fn is_root(ints: &[i64], p: i64, q: i64) -> bool {
+    let (p, q) = (p as i128, q as i128);
+    let mut acc: i128 = 0;
+    let mut q_pow: i128 = 1;
+    for (i, &a) in ints.iter().enumerate() {
+        if i > 0 {
+            q_pow = match q_pow.checked_mul(q) {
+                Some(q_pow) => q_pow,
+                None => return false
+            };
+        }
+        acc = match acc.checked_mul(p).and_then(|x| (a as i128).checked_mul(q_pow).and_then(|y| x.checked_add(y))) {
+            Some(acc) => acc,
+            None => return false
+        };
+    }
+    acc == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use fracs::Frac;
+    use poly::Polynomial;
+
+    fn poly(coeffs: &[(i32, i32)]) -> Polynomial<Frac> {
+        Polynomial::new(coeffs.iter().map(|&(num, den)| Frac::new(num, den)).collect())
+    }
+
+    #[test]
+    fn rational_roots_with_multiplicity() {
+        // (2λ - 1)^2 (λ + 3) λ = 4λ^4 + 8λ^3 - 11λ^2 + 3λ
+        let p = poly(&[(4, 1), (8, 1), (-11, 1), (3, 1), (0, 1)]);
+        let (roots, rest) = p.rational_roots();
+        assert_eq!(roots, vec![(Frac::from(-3), 1), (Frac::from(0), 1), (Frac::new(1, 2), 2)]);
+        assert_eq!(rest, poly(&[(4, 1)]));
+    }
+
+    #[test]
+    fn irrational_roots_are_left_over() {
+        // (λ - 2/3)(λ^2 - 2)
+        let p = poly(&[(1, 1), (-2, 3), (-2, 1), (4, 3)]);
+        let (roots, rest) = p.rational_roots();
+        assert_eq!(roots, vec![(Frac::new(2, 3), 1)]);
+        assert_eq!(rest, poly(&[(1, 1), (0, 1), (-2, 1)]));
+    }
+
+    #[test]
+    fn integer_coeffs_clear_denominators() {
+        assert_eq!(poly(&[(1, 2), (-1, 3), (1, 6)]).integer_coeffs(), Some(vec![3, -2, 1]));
+        assert_eq!(poly(&[(4, 1), (-6, 1)]).integer_coeffs(), Some(vec![2, -3]));
+    }
+
+    #[test]
+    fn huge_denominators_find_nothing() {
+        let p = poly(&[(1, 2147483647), (1, 2147483629), (1, 2147483587), (1, 2147483579)]);
+        assert_eq!(p.integer_coeffs(), None);
+        assert_eq!(p.rational_roots(), (Vec::new(), p.clone()));
+    }
+}
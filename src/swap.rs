@@ -0,0 +1,181 @@
+#![allow(dead_code)]
+
+// Pivoting strategies for elimination. When the entry where the next pivot should go is zero (or
+// just isn't a nice number), a strategy picks which row gets swapped into its place.
+
+use fracs::{Frac, WideFrac};
+use field::{Field, ModInt};
+use mats::Matrix;
+
+pub trait PivotStrategy<T: Field> {
+    // Picks the row to pivot on out of rows `from..` in column `col`. Has to return a row with a
+    // nonzero entry there, or None if every candidate is zero.
+    fn choose(&self, matr: &Matrix<T>, col: usize, from: usize) -> Option<usize>;
+}
+
+// What the smarter strategies need to compare candidate pivots
+pub trait PivotMeasure {
+    // Size of the entry, for partial pivoting
+    fn magnitude(&self) -> f64;
+
+    // How unpleasant the entry is to divide by in hand work. Smaller is nicer, and ±1 should be
+    // the smallest nonzero value.
+    fn complexity(&self) -> u64;
+}
+
+impl PivotMeasure for Frac {
+    fn magnitude(&self) -> f64 {
+        (self.num as f64 / self.den as f64).abs()
+    }
+
+    fn complexity(&self) -> u64 {
+        let simple = self.try_simplify();
+        (simple.num as i64).unsigned_abs() + (simple.den as i64).unsigned_abs()
+    }
+}
+
+impl PivotMeasure for WideFrac {
+    fn magnitude(&self) -> f64 {
+        (self.num as f64 / self.den as f64).abs()
+    }
+
+    fn complexity(&self) -> u64 {
+        self.num.unsigned_abs().saturating_add(self.den.unsigned_abs())
+    }
+}
+
+// Whole numbers are as simple as their size, anything else is as bad as it gets
+impl PivotMeasure for f64 {
+    fn magnitude(&self) -> f64 {
+        self.abs()
+    }
+
+    fn complexity(&self) -> u64 {
+        if self.fract() == 0.0 && self.abs() < u64::MAX as f64 {
+            self.abs() as u64 + 1
+        } else {
+            u64::MAX
+        }
+    }
+}
+
+// There's no size in a finite field, so every nonzero element is equally good
+impl<const P: u32> PivotMeasure for ModInt<P> {
+    fn magnitude(&self) -> f64 {
+        1.0
+    }
+
+    fn complexity(&self) -> u64 {
+        1
+    }
+}
+
+fn candidates<'a, T: Field>(matr: &'a Matrix<T>, col: usize, from: usize) -> impl Iterator<Item = (usize, T)> + 'a {
+    (from..matr.dimension().0).map(move |row| (row, matr[(row, col)])).filter(|&(_, val)| !val.is_zero())
+}
+
+// Keeps the current row unless its entry is zero, in which case the first nonzero row beneath it
+// is swapped up. Fewest swaps, and what the default elimination uses.
+pub struct FirstNonzero;
+
+impl<T: Field> PivotStrategy<T> for FirstNonzero {
+    fn choose(&self, matr: &Matrix<T>, col: usize, from: usize) -> Option<usize> {
+        candidates(matr, col, from).map(|(row, _)| row).next()
+    }
+}
+
+// Picks the simplest fraction in the column (±1 if there is one), breaking ties by smallest
+// absolute value and then by position. Keeps hand-worked steps readable.
+pub struct SimplestPivot;
+
+impl<T: Field + PivotMeasure> PivotStrategy<T> for SimplestPivot {
+    fn choose(&self, matr: &Matrix<T>, col: usize, from: usize) -> Option<usize> {
+        let mut best: Option<(usize, u64, f64)> = None;
+        for (row, val) in candidates(matr, col, from) {
+            let (complexity, magnitude) = (val.complexity(), val.magnitude());
+            best = match best {
+                Some((_, c, m)) if (c, m) <= (complexity, magnitude) => best,
+                _ => Some((row, complexity, magnitude))
+            };
+        }
+        best.map(|(row, _, _)| row)
+    }
+}
+
+// Partial pivoting: the entry with the largest absolute value, which keeps floating point
+// elimination stable. The earliest row wins ties.
+pub struct PartialPivot;
+
+impl<T: Field + PivotMeasure> PivotStrategy<T> for PartialPivot {
+    fn choose(&self, matr: &Matrix<T>, col: usize, from: usize) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (row, val) in candidates(matr, col, from) {
+            let magnitude = val.magnitude();
+            best = match best {
+                Some((_, m)) if m >= magnitude => best,
+                _ => Some((row, magnitude))
+            };
+        }
+        best.map(|(row, _)| row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use field::EPSILON;
+    use fracs::Frac;
+    use mats::Matrix;
+    use mats::format::Silent;
+    use mats::steps::{RowOp, StepLog};
+    use swap::*;
+
+    fn column(entries: &str) -> Matrix {
+        entries.parse().unwrap()
+    }
+
+    #[test]
+    fn strategies_pick_their_rows() {
+        let col = column("0; 3; 1/2; -1; 1");
+        assert_eq!(FirstNonzero.choose(&col, 0, 0), Some(1));
+        assert_eq!(FirstNonzero.choose(&col, 0, 2), Some(2));
+        // -1 and 1 are equally simple, so the earlier one wins
+        assert_eq!(SimplestPivot.choose(&col, 0, 0), Some(3));
+        assert_eq!(PartialPivot.choose(&col, 0, 0), Some(1));
+        assert_eq!(PartialPivot.choose(&column("1; -7; 7"), 0, 0), Some(1));
+        assert_eq!(FirstNonzero.choose(&column("5; 0; 0"), 0, 1), None);
+        assert_eq!(SimplestPivot.choose(&column("0; 0"), 0, 0), None);
+    }
+
+    #[test]
+    fn measures() {
+        assert_eq!(Frac::new(-2, 4).complexity(), 3);
+        assert_eq!(Frac::new(-2, 4).magnitude(), 0.5);
+        assert_eq!(2.0f64.complexity(), 3);
+        assert_eq!(0.5f64.complexity(), u64::MAX);
+    }
+
+    #[test]
+    fn strategies_plug_into_elimination() {
+        let a = column("2, 1; 1, 3");
+        let steps = {
+            let mut log = StepLog::new();
+            a.clone().row_echelon_form_with(&SimplestPivot, &mut log);
+            log.steps
+        };
+        assert_eq!(steps[0].op, RowOp::Swap(0, 1));
+        let inverse = a.inverse(&mut Silent).unwrap();
+        assert_eq!(a.inverse_with(&PartialPivot, &mut Silent), Ok(inverse.clone()));
+        assert_eq!(a.inverse_with(&SimplestPivot, &mut Silent), Ok(inverse));
+
+        // The tiny entry gets swapped away from the pivot instead of being divided by
+        let mut floats = "1e-9, 1; 1, 1".parse::<Matrix<f64>>().unwrap();
+        let steps = {
+            let mut log = StepLog::new();
+            floats.reduced_row_echelon_form_with(&PartialPivot, &mut log);
+            log.steps
+        };
+        assert_eq!(steps[0].op, RowOp::Swap(0, 1));
+        let identity = Matrix::<f64>::identity(2);
+        assert!(floats.iter().all(|(pos, elem)| (elem - identity[pos]).abs() < EPSILON));
+    }
+}
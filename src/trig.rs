@@ -0,0 +1,307 @@
+#![allow(dead_code)]
+
+// Rational trigonometry (Wildberger): distances and angles are replaced by quadrance (squared
+// distance) and spread (squared sine of the angle between two lines). Both stay rational when the
+// coordinates are rational, so triangles can be solved exactly with fracs::Frac and no square roots.
+
+use std::fmt;
+
+use fracs::Frac;
+
+#[derive(Clone, Copy)]
+pub struct Point {
+    pub x: Frac,
+    pub y: Frac
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {}]", self.x, self.y)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from(tup: (i32, i32)) -> Self {
+        Point {
+            x: Frac::from(tup.0),
+            y: Frac::from(tup.1)
+        }
+    }
+}
+
+impl Point {
+    pub fn new(x: Frac, y: Frac) -> Self {
+        Point { x, y }
+    }
+}
+
+// The line a * x + b * y + c = 0, written ⟨a : b : c⟩. Only the proportion matters, and a and b
+// can't both be zero.
+#[derive(Clone, Copy)]
+pub struct Line {
+    pub a: Frac,
+    pub b: Frac,
+    pub c: Frac
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "⟨{} : {} : {}⟩", self.a, self.b, self.c)
+    }
+}
+
+impl Line {
+    pub fn new(a: Frac, b: Frac, c: Frac) -> Result<Line, String> {
+        if a.num == 0 && b.num == 0 {
+            return Err(String::from("⟨0 : 0 : c⟩ isn't a line."));
+        }
+        Ok(Line { a, b, c })
+    }
+
+    // The line through two distinct points: ⟨y1 - y2 : x2 - x1 : x1 * y2 - x2 * y1⟩
+    pub fn through(p1: Point, p2: Point) -> Result<Line, String> {
+        Line::new(p1.y.sub(p2.y), p2.x.sub(p1.x), p1.x.mul(p2.y).sub(p2.x.mul(p1.y)))
+            .map_err(|_| String::from("Need two distinct points to make a line."))
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.a.mul(p.x).add(self.b.mul(p.y)).add(self.c).num == 0
+    }
+
+    pub fn is_parallel(&self, other: &Line) -> bool {
+        spread(self, other).num == 0
+    }
+
+    pub fn is_perpendicular(&self, other: &Line) -> bool {
+        cross(self, other).num == 0
+    }
+}
+
+fn sq(x: Frac) -> Frac {
+    x.mul(x)
+}
+
+// Q(A, B) = (x2 - x1)^2 + (y2 - y1)^2
+pub fn quadrance(p1: Point, p2: Point) -> Frac {
+    sq(p2.x.sub(p1.x)).add(sq(p2.y.sub(p1.y)))
+}
+
+// s(l1, l2) = (a1 * b2 - a2 * b1)^2 / ((a1^2 + b1^2) * (a2^2 + b2^2)); 0 for parallel lines and 1
+// for perpendicular ones
+pub fn spread(l1: &Line, l2: &Line) -> Frac {
+    let num = sq(l1.a.mul(l2.b).sub(l2.a.mul(l1.b)));
+    num.div(sq(l1.a).add(sq(l1.b)).mul(sq(l2.a).add(sq(l2.b))))
+}
+
+// c(l1, l2) = 1 - s(l1, l2) = (a1 * a2 + b1 * b2)^2 / ((a1^2 + b1^2) * (a2^2 + b2^2))
+pub fn cross(l1: &Line, l2: &Line) -> Frac {
+    let num = sq(l1.a.mul(l2.a).add(l1.b.mul(l2.b)));
+    num.div(sq(l1.a).add(sq(l1.b)).mul(sq(l2.a).add(sq(l2.b))))
+}
+
+// Triple quad formula: three points are collinear exactly when
+// (Q1 + Q2 + Q3)^2 = 2 * (Q1^2 + Q2^2 + Q3^2)
+pub fn triple_quad(q1: Frac, q2: Frac, q3: Frac) -> bool {
+    sq(q1.add(q2).add(q3)) == Frac::from(2).mul(sq(q1).add(sq(q2)).add(sq(q3)))
+}
+
+// Pythagoras' theorem: the lines meeting at the vertex opposite Q3 are perpendicular exactly when
+// Q1 + Q2 = Q3
+pub fn pythagoras(q1: Frac, q2: Frac, q3: Frac) -> bool {
+    q1.add(q2) == q3
+}
+
+// Triple spread formula, satisfied by the three spreads of any triangle:
+// (s1 + s2 + s3)^2 = 2 * (s1^2 + s2^2 + s3^2) + 4 * s1 * s2 * s3
+pub fn triple_spread(s1: Frac, s2: Frac, s3: Frac) -> bool {
+    sq(s1.add(s2).add(s3)) == Frac::from(2).mul(sq(s1).add(sq(s2)).add(sq(s3)))
+        .add(Frac::from(4).mul(s1).mul(s2).mul(s3))
+}
+
+// Spread law: s1 / Q1 = s2 / Q2 = s3 / Q3. Cross-multiplied so zero quadrances don't blow up.
+pub fn spread_law(q: (Frac, Frac, Frac), s: (Frac, Frac, Frac)) -> bool {
+    s.0.mul(q.1) == s.1.mul(q.0) && s.1.mul(q.2) == s.2.mul(q.1)
+}
+
+// Cross law: (Q1 + Q2 - Q3)^2 = 4 * Q1 * Q2 * (1 - s3), solved for the spread s3 opposite Q3
+pub fn cross_law_spread(q1: Frac, q2: Frac, q3: Frac) -> Result<Frac, String> {
+    if q1.num == 0 || q2.num == 0 {
+        return Err(String::from("The spread at a vertex needs both adjacent quadrances to be nonzero."));
+    }
+    let lhs = sq(q1.add(q2).sub(q3));
+    Ok(Frac::from(1).sub(lhs.div(Frac::from(4).mul(q1).mul(q2))))
+}
+
+// Exact square root of a fraction, if it has one
+pub fn rational_sqrt(x: Frac) -> Option<Frac> {
+    fn int_sqrt(n: i32) -> Option<i32> {
+        if n < 0 {
+            return None;
+        }
+        let root = (n as f64).sqrt().round() as i32;
+        (root - 1..root + 2).find(|&r| r >= 0 && (r as i64) * (r as i64) == n as i64)
+    }
+    let x = x.try_simplify();
+    match (int_sqrt(x.num), int_sqrt(x.den)) {
+        (Some(num), Some(den)) => Some(Frac::new(num, den)),
+        _ => None
+    }
+}
+
+// Both roots of s^2 - 2 * m * s + k = 0, keeping only the rational ones
+fn rational_roots(m: Frac, k: Frac) -> Vec<Frac> {
+    match rational_sqrt(sq(m).sub(k)) {
+        None => Vec::new(),
+        Some(r) if r.num == 0 => vec![m],
+        Some(r) => vec![m.sub(r), m.add(r)]
+    }
+}
+
+// Cross law solved for Q3 given Q1, Q2 and the spread s3 between them:
+// Q3 = Q1 + Q2 ± 2 * √(Q1 * Q2 * (1 - s3)). Only the rational solutions are returned.
+pub fn cross_law_quadrances(q1: Frac, q2: Frac, s3: Frac) -> Vec<Frac> {
+    let m = q1.add(q2);
+    let k = sq(q1.sub(q2)).add(Frac::from(4).mul(q1).mul(q2).mul(s3));
+    rational_roots(m, k)
+}
+
+// Triple spread formula solved for s3 given s1 and s2:
+// s3 = s1 + s2 - 2 * s1 * s2 ± 2 * √(s1 * s2 * (1 - s1) * (1 - s2)). Only the rational solutions
+// are returned.
+pub fn triple_spread_third(s1: Frac, s2: Frac) -> Vec<Frac> {
+    let m = s1.add(s2).sub(Frac::from(2).mul(s1).mul(s2));
+    rational_roots(m, sq(s1.sub(s2)))
+}
+
+// Points A, B, C. Quadrances and spreads are numbered by the vertex they're opposite/at, so Q1 is
+// the quadrance of BC and s1 is the spread at A.
+#[derive(Clone, Copy)]
+pub struct Triangle {
+    pub a: Point,
+    pub b: Point,
+    pub c: Point
+}
+
+impl Triangle {
+    pub fn new(a: Point, b: Point, c: Point) -> Self {
+        Triangle { a, b, c }
+    }
+
+    pub fn quadrances(&self) -> (Frac, Frac, Frac) {
+        (quadrance(self.b, self.c), quadrance(self.c, self.a), quadrance(self.a, self.b))
+    }
+
+    pub fn is_degenerate(&self) -> bool {
+        let (q1, q2, q3) = self.quadrances();
+        triple_quad(q1, q2, q3)
+    }
+
+    // Spreads from the quadrances alone via the cross law
+    pub fn spreads(&self) -> Result<(Frac, Frac, Frac), String> {
+        let (q1, q2, q3) = self.quadrances();
+        solve_quadrances(q1, q2, q3)
+    }
+
+    // Quadrea A = 16 * area^2, from Archimedes' formula A = (Q1 + Q2 + Q3)^2 - 2 * (Q1^2 + Q2^2 + Q3^2)
+    pub fn quadrea(&self) -> Frac {
+        let (q1, q2, q3) = self.quadrances();
+        sq(q1.add(q2).add(q3)).sub(Frac::from(2).mul(sq(q1).add(sq(q2)).add(sq(q3))))
+    }
+}
+
+// Solves a triangle given all three quadrances, returning the three spreads. Fails if the
+// quadrances describe a degenerate (collinear) triangle.
+pub fn solve_quadrances(q1: Frac, q2: Frac, q3: Frac) -> Result<(Frac, Frac, Frac), String> {
+    if triple_quad(q1, q2, q3) {
+        return Err(String::from("Quadrances satisfy the triple quad formula, so the points are collinear."));
+    }
+    Ok((cross_law_spread(q2, q3, q1)?, cross_law_spread(q3, q1, q2)?, cross_law_spread(q1, q2, q3)?))
+}
+
+// Solves a triangle given all three spreads and one quadrance using the spread law, returning all
+// three quadrances
+pub fn solve_spreads(s: (Frac, Frac, Frac), q1: Frac) -> Result<(Frac, Frac, Frac), String> {
+    if s.0.num == 0 {
+        return Err(String::from("The spread opposite the known quadrance can't be zero."));
+    }
+    if !triple_spread(s.0, s.1, s.2) {
+        return Err(String::from("Spreads don't satisfy the triple spread formula."));
+    }
+    let ratio = q1.div(s.0);
+    Ok((q1, s.1.mul(ratio), s.2.mul(ratio)))
+}
+
+#[cfg(test)]
+mod tests {
+    use fracs::Frac;
+    use trig::*;
+
+    fn f(num: i32, den: i32) -> Frac {
+        Frac::new(num, den)
+    }
+
+    #[test]
+    fn quadrance_and_spread() {
+        assert_eq!(quadrance(Point::from((0, 0)), Point::from((3, 4))), Frac::from(25));
+        let x_axis = Line::through(Point::from((0, 0)), Point::from((1, 0))).unwrap();
+        let y_axis = Line::through(Point::from((0, 0)), Point::from((0, 1))).unwrap();
+        let diagonal = Line::through(Point::from((0, 0)), Point::from((1, 1))).unwrap();
+        assert_eq!(spread(&x_axis, &y_axis), Frac::from(1));
+        assert_eq!(cross(&x_axis, &y_axis), Frac::from(0));
+        assert_eq!(spread(&x_axis, &diagonal), f(1, 2));
+        assert!(x_axis.is_perpendicular(&y_axis));
+        assert!(x_axis.is_parallel(&Line::new(Frac::from(0), Frac::from(2), Frac::from(5)).unwrap()));
+        assert!(diagonal.contains(Point::from((-3, -3))));
+        assert!(Line::new(Frac::from(0), Frac::from(0), Frac::from(1)).is_err());
+        assert!(Line::through(Point::from((1, 1)), Point::from((1, 1))).is_err());
+    }
+
+    #[test]
+    fn rational_square_roots() {
+        assert_eq!(rational_sqrt(f(9, 4)), Some(f(3, 2)));
+        assert_eq!(rational_sqrt(f(18, 8)), Some(f(3, 2)));
+        assert_eq!(rational_sqrt(Frac::from(2147395600)), Some(Frac::from(46340)));
+        assert_eq!(rational_sqrt(Frac::from(2)), None);
+        assert_eq!(rational_sqrt(Frac::from(-4)), None);
+    }
+
+    #[test]
+    fn right_triangle_laws() {
+        // A = (0, 0), B = (3, 0), C = (0, 4): the right angle is at A
+        let tri = Triangle::new(Point::from((0, 0)), Point::from((3, 0)), Point::from((0, 4)));
+        let (q1, q2, q3) = tri.quadrances();
+        assert_eq!((q1, q2, q3), (Frac::from(25), Frac::from(16), Frac::from(9)));
+        assert!(pythagoras(q2, q3, q1));
+        assert!(!triple_quad(q1, q2, q3));
+        assert!(!tri.is_degenerate());
+        assert_eq!(tri.quadrea(), Frac::from(576));
+
+        let spreads = tri.spreads().unwrap();
+        assert_eq!(spreads, (Frac::from(1), f(16, 25), f(9, 25)));
+        assert!(triple_spread(spreads.0, spreads.1, spreads.2));
+        assert!(spread_law((q1, q2, q3), spreads));
+        assert_eq!(solve_spreads(spreads, q1), Ok((q1, q2, q3)));
+        assert_eq!(cross_law_spread(q2, q3, q1), Ok(Frac::from(1)));
+    }
+
+    #[test]
+    fn solving_for_the_missing_side_or_spread() {
+        assert_eq!(cross_law_quadrances(Frac::from(16), Frac::from(9), Frac::from(1)), vec![Frac::from(25)]);
+        // Spread 0 means the points are collinear, so Q3 is (4 - 3)^2 or (4 + 3)^2
+        assert_eq!(cross_law_quadrances(Frac::from(16), Frac::from(9), Frac::from(0)),
+                   vec![Frac::from(1), Frac::from(49)]);
+        assert!(cross_law_quadrances(Frac::from(1), Frac::from(1), f(1, 2)).is_empty());
+        assert_eq!(triple_spread_third(f(16, 25), f(9, 25)), vec![f(49, 625), Frac::from(1)]);
+    }
+
+    #[test]
+    fn degenerate_triangles_are_rejected() {
+        let tri = Triangle::new(Point::from((0, 0)), Point::from((1, 1)), Point::from((2, 2)));
+        assert!(tri.is_degenerate());
+        assert_eq!(tri.quadrea(), Frac::from(0));
+        assert!(tri.spreads().is_err());
+        assert!(cross_law_spread(Frac::from(0), Frac::from(1), Frac::from(1)).is_err());
+        assert!(solve_spreads((Frac::from(0), Frac::from(1), Frac::from(1)), Frac::from(1)).is_err());
+        assert!(solve_spreads((Frac::from(1), Frac::from(1), Frac::from(1)), Frac::from(1)).is_err());
+    }
+}